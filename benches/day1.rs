@@ -0,0 +1,46 @@
+//! Compares the day 1 part 2 trie scan with the Aho-Corasick automaton.
+//!
+//! Run with `cargo bench --bench day1`. Uses `inputs/day01.txt` when it exists, plus a
+//! long line that is worst case for the trie.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use aoc2023::day1::{get_value_part2, get_value_part2_trie};
+
+const RUNS: usize = 20;
+
+fn bench(name: &str, lines: &[String], f: fn(&str) -> usize) -> usize {
+    let mut best = Duration::MAX;
+    let mut total = 0;
+    for _ in 0..RUNS {
+        let now = Instant::now();
+        total = lines.iter().map(|line| f(black_box(line))).sum();
+        best = best.min(now.elapsed());
+    }
+    println!("  {name:<14} {best:>12.2?}");
+    total
+}
+
+fn compare(label: &str, lines: &[String]) {
+    println!("{label} (best of {RUNS})");
+    let trie = bench("trie", lines, get_value_part2_trie);
+    let automaton = bench("aho-corasick", lines, get_value_part2);
+    assert_eq!(trie, automaton, "implementations disagree on {label}");
+}
+
+fn main() {
+    match std::fs::read_to_string("inputs/day01.txt") {
+        Ok(input) => compare(
+            "inputs/day01.txt",
+            &input.lines().map(String::from).collect::<Vec<_>>(),
+        ),
+        Err(err) => println!("skipping inputs/day01.txt: {err}"),
+    }
+
+    // Almost-words at every offset make the trie walk several bytes before giving up, all
+    // the way to the digit in the middle from either end
+    let near_misses = "sevesieighnin".repeat(2_500);
+    let line = format!("{near_misses}1{near_misses}");
+    compare("near misses", &[line]);
+}
@@ -0,0 +1,42 @@
+//! Prints the smallest bag each day 2 game could have been played with, and statistics
+//! across the games.
+//!
+//! `cargo run --example day2_report -- [--json] [INPUT]` prints a table, or JSON with
+//! `--json`. INPUT defaults to `inputs/day02.txt`.
+
+use std::process::ExitCode;
+
+use aoc2023::day2::{parse_games, BagReport};
+
+fn main() -> ExitCode {
+    let mut json = false;
+    let mut input = "inputs/day02.txt".to_string();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            _ => input = arg,
+        }
+    }
+
+    let text = match std::fs::read_to_string(&input) {
+        Ok(text) => text,
+        Err(err) => {
+            eprintln!("error: could not read {input}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let games = match parse_games(&text, None) {
+        Ok(games) => games,
+        Err(err) => {
+            eprintln!("{}", err.diagnostic(&text, &input));
+            return ExitCode::FAILURE;
+        }
+    };
+    let report = BagReport::new(&games);
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        print!("{}", report.table());
+    }
+    ExitCode::SUCCESS
+}
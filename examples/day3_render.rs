@@ -0,0 +1,53 @@
+//! Draws a day 3 schematic with part numbers, other numbers and gears marked.
+//!
+//! `cargo run --example day3_render -- [--html OUT] [INPUT]` prints it in colour, or
+//! writes an HTML page to OUT. INPUT defaults to `inputs/day03.txt`.
+
+use std::process::ExitCode;
+
+use aoc2023::day3::{GearRule, Schematic};
+
+fn main() -> ExitCode {
+    let mut html = None;
+    let mut input = "inputs/day03.txt".to_string();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--html" => match args.next() {
+                Some(path) => html = Some(path),
+                None => {
+                    eprintln!("error: --html needs a path");
+                    return ExitCode::from(2);
+                }
+            },
+            _ => input = arg,
+        }
+    }
+
+    let text = match std::fs::read_to_string(&input) {
+        Ok(text) => text,
+        Err(err) => {
+            eprintln!("error: could not read {input}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let schematic = match Schematic::parse(&text) {
+        Ok(schematic) => schematic,
+        Err(err) => {
+            eprintln!("{}", err.diagnostic(&text, &input));
+            return ExitCode::FAILURE;
+        }
+    };
+    let rule = GearRule::puzzle();
+    match html {
+        Some(path) => {
+            if let Err(err) = std::fs::write(&path, schematic.render_html(&rule)) {
+                eprintln!("error: could not write {path}: {err}");
+                return ExitCode::FAILURE;
+            }
+            eprintln!("Wrote {path}");
+        }
+        None => print!("{}", schematic.render_ansi(&rule)),
+    }
+    ExitCode::SUCCESS
+}
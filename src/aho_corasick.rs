@@ -0,0 +1,148 @@
+//! Multi-pattern string search: finds every occurrence of every pattern in one pass over
+//! the haystack, including matches that overlap.
+
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// Index of the pattern in the list the automaton was built from.
+    pub pattern: usize,
+    /// Byte range of the match in the haystack.
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    /// Trie edges, sorted by byte.
+    next: Vec<(u8, usize)>,
+    /// Node for the longest proper suffix of this node's path that is also in the trie.
+    fail: usize,
+    /// Patterns ending at this node, longest first, including those reached through `fail`.
+    out: Vec<usize>,
+}
+
+impl Node {
+    fn get(&self, b: u8) -> Option<usize> {
+        let i = self.next.binary_search_by_key(&b, |&(b, _)| b).ok()?;
+        Some(self.next[i].1)
+    }
+}
+
+/// An Aho-Corasick automaton over bytes, so patterns and haystacks can be any UTF-8 text.
+#[derive(Debug)]
+pub struct AhoCorasick {
+    nodes: Vec<Node>,
+    /// `delta[node * 256 + b]` is the state after reading `b` in `node`, with the failure
+    /// links already followed, so a search does one lookup per byte.
+    delta: Vec<u32>,
+    lens: Vec<usize>,
+}
+
+impl AhoCorasick {
+    /// Builds the automaton. Patterns must not be empty.
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<[u8]>,
+    {
+        let mut nodes = vec![Node::default()];
+        let mut lens = Vec::new();
+        for (id, pattern) in patterns.into_iter().enumerate() {
+            let pattern = pattern.as_ref();
+            assert!(!pattern.is_empty(), "empty pattern {id}");
+            let mut node = 0;
+            for &b in pattern {
+                node = match nodes[node].next.binary_search_by_key(&b, |&(b, _)| b) {
+                    Ok(i) => nodes[node].next[i].1,
+                    Err(i) => {
+                        nodes.push(Node::default());
+                        let new = nodes.len() - 1;
+                        nodes[node].next.insert(i, (b, new));
+                        new
+                    }
+                };
+            }
+            nodes[node].out.push(id);
+            lens.push(pattern.len());
+        }
+
+        // Breadth first, so a node's failure link is finished before its children need it
+        let mut queue: VecDeque<usize> = nodes[0].next.iter().map(|&(_, n)| n).collect();
+        while let Some(node) = queue.pop_front() {
+            for (b, child) in nodes[node].next.clone() {
+                let mut fail = nodes[node].fail;
+                let fail = loop {
+                    match nodes[fail].get(b) {
+                        Some(n) => break n,
+                        None if fail == 0 => break 0,
+                        None => fail = nodes[fail].fail,
+                    }
+                };
+                nodes[child].fail = fail;
+                let inherited = nodes[fail].out.clone();
+                nodes[child].out.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+
+        // Also breadth first: a missing edge copies the failure node's, which is shallower
+        let mut delta = vec![0; nodes.len() * 256];
+        let mut queue = VecDeque::from([0]);
+        while let Some(node) = queue.pop_front() {
+            for b in 0..256 {
+                delta[node * 256 + b] = match nodes[node].get(b as u8) {
+                    Some(n) => n as u32,
+                    None if node == 0 => 0,
+                    None => delta[nodes[node].fail * 256 + b],
+                };
+            }
+            queue.extend(nodes[node].next.iter().map(|&(_, n)| n));
+        }
+        AhoCorasick { nodes, delta, lens }
+    }
+
+    /// Every match in `haystack`, ordered by end. Matches ending at the same place come
+    /// longest first.
+    pub fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> impl Iterator<Item = Match> + 'a {
+        haystack
+            .iter()
+            .scan(0, move |node, &b| {
+                *node = self.delta[*node * 256 + b as usize] as usize;
+                Some(*node)
+            })
+            .enumerate()
+            .filter(move |&(_, node)| !self.nodes[node].out.is_empty())
+            .flat_map(move |(i, node)| {
+                self.nodes[node].out.iter().map(move |&pattern| Match {
+                    pattern,
+                    start: i + 1 - self.lens[pattern],
+                    end: i + 1,
+                })
+            })
+    }
+}
+
+#[test]
+fn test() {
+    let ac = AhoCorasick::new(["he", "she", "his", "hers"]);
+    let found: Vec<_> = ac
+        .find_iter(b"ushers")
+        .map(|m| (m.pattern, m.start, m.end))
+        .collect();
+    assert_eq!(found, [(1, 1, 4), (0, 2, 4), (3, 2, 6)]);
+
+    let ac = AhoCorasick::new(["aa", "a"]);
+    assert_eq!(ac.find_iter(b"aaa").count(), 5);
+    assert_eq!(ac.find_iter(b"").count(), 0);
+
+    let ac = AhoCorasick::new(["zwölf", "elf"]);
+    let found: Vec<_> = ac.find_iter("elfzwölf".as_bytes()).collect();
+    let zwölf = Match {
+        pattern: 0,
+        start: 3,
+        end: 9,
+    };
+    assert_eq!(found[1], zwölf);
+    assert_eq!(found.len(), 2);
+}
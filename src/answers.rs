@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use aoc2023::ParseError;
+
+/// File answers are checked against unless overridden on the command line.
+pub const ANSWERS_PATH: &str = "answers.toml";
+
+const HEADER: &str =
+    "# Known-good answers, checked on every run. Update with `aoc2023 --record`.\n";
+
+/// How a computed answer compares with the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// Nothing recorded for this day and part with this input yet.
+    New,
+}
+
+impl Status {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Status::Pass => "PASS",
+            Status::Fail { .. } => "FAIL",
+            Status::New => "NEW",
+        }
+    }
+}
+
+/// Recorded answers, stored as a small TOML file with a `[dayNN.<input hash>]` table per
+/// day and input and `part1`/`part2` string keys. Everyone's puzzle input differs, so
+/// answers only count for the input they were computed from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    days: BTreeMap<(usize, String), [Option<String>; 2]>,
+}
+
+impl Answers {
+    /// Loads `path`, treating a missing file as having no answers. Errors come back
+    /// ready to print.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => {
+                Self::parse(&text).map_err(|e| e.diagnostic(&text, &path.display().to_string()))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(format!("error: could not read {}: {err}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_toml())
+    }
+
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut answers = Answers::default();
+        let mut day = None;
+        for line in text.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if let Some(table) = trimmed.strip_prefix('[') {
+                let (n, hash) = table
+                    .strip_suffix(']')
+                    .and_then(|t| t.strip_prefix("day"))
+                    .and_then(|t| t.split_once('.'))
+                    .and_then(|(n, hash)| Some((n.parse().ok()?, hash)))
+                    .filter(|(_, hash)| is_hash(hash))
+                    .ok_or_else(|| ParseError::at(text, trimmed, TABLE))?;
+                day = Some((n, hash.to_string()));
+                continue;
+            }
+
+            let table = day
+                .clone()
+                .ok_or_else(|| ParseError::at(text, trimmed, TABLE))?;
+            let (key, value) = trimmed
+                .split_once('=')
+                .ok_or_else(|| ParseError::at(text, trimmed, "`<key> = \"<answer>\"`"))?;
+            let (key, value) = (key.trim(), value.trim());
+            let part = match key {
+                "part1" => 0,
+                "part2" => 1,
+                _ => return Err(ParseError::at(text, key, "`part1` or `part2`")),
+            };
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .ok_or_else(|| ParseError::at(text, value, "a quoted answer"))?;
+            answers.days.entry(table).or_default()[part] = Some(value.to_string());
+        }
+        Ok(answers)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut toml = HEADER.to_string();
+        for ((day, hash), parts) in &self.days {
+            write!(toml, "\n[day{day:02}.{hash}]\n").unwrap();
+            for (i, answer) in parts.iter().enumerate() {
+                if let Some(answer) = answer {
+                    writeln!(toml, "part{} = \"{answer}\"", i + 1).unwrap();
+                }
+            }
+        }
+        toml
+    }
+
+    /// Compares `answer` with what is recorded for `part` (1 or 2) of `day` run on the
+    /// input with hash `input_hash`.
+    pub fn check(&self, day: usize, input_hash: &str, part: usize, answer: &str) -> Status {
+        match self
+            .days
+            .get(&(day, input_hash.to_string()))
+            .and_then(|parts| parts[part - 1].as_ref())
+        {
+            None => Status::New,
+            Some(expected) if expected == answer => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.clone(),
+            },
+        }
+    }
+
+    pub fn record(&mut self, day: usize, input_hash: &str, part: usize, answer: &str) {
+        let parts = self.days.entry((day, input_hash.to_string())).or_default();
+        parts[part - 1] = Some(answer.to_string());
+    }
+}
+
+const TABLE: &str = "a `[dayNN.<input hash>]` table";
+
+fn is_hash(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[test]
+fn test() {
+    let text = "# comment
+
+[day01.0123456789abcdef]
+part1 = \"55172\"
+part2 = \"54925\"
+
+[day08.fedcba9876543210]
+part1 = \"19783\"
+";
+    let mut answers = Answers::parse(text).unwrap();
+    assert_eq!(
+        answers.check(1, "0123456789abcdef", 1, "55172"),
+        Status::Pass
+    );
+    assert_eq!(
+        answers.check(1, "0123456789abcdef", 2, "1"),
+        Status::Fail {
+            expected: "54925".to_string()
+        }
+    );
+    assert_eq!(answers.check(8, "fedcba9876543210", 2, "1"), Status::New);
+    assert_eq!(answers.check(3, "0123456789abcdef", 1, "1"), Status::New);
+    // Someone else's input
+    assert_eq!(answers.check(1, "1111111111111111", 1, "1"), Status::New);
+
+    answers.record(8, "fedcba9876543210", 2, "9177460370549");
+    answers.record(1, "1111111111111111", 1, "54321");
+    assert_eq!(Answers::parse(&answers.to_toml()).unwrap(), answers);
+    assert_eq!(
+        answers.check(1, "0123456789abcdef", 1, "55172"),
+        Status::Pass
+    );
+
+    let err = Answers::parse("[day01.0a]\npart3 = \"1\"").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 1));
+    assert!(Answers::parse("part1 = \"1\"").is_err());
+    let err = Answers::parse("[day01]\npart1 = \"1\"").unwrap_err();
+    assert_eq!(err.expected, TABLE);
+}
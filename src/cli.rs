@@ -0,0 +1,246 @@
+use std::path::PathBuf;
+
+use crate::answers::ANSWERS_PATH;
+use crate::input::InputSource;
+use crate::report::Format;
+use crate::Parts;
+
+pub const USAGE: &str =
+    "Usage: aoc2023 [DAYS...] [--part 1|2] [--all] [--input <PATH>] [--record] [--bench <N>]
+                      [--format text|json] [--jobs <N>]
+
+Arguments:
+  DAYS             Days to run, as single days or ranges (e.g. `1-5 8`).
+                   Defaults to the most recent day.
+
+Options:
+  -p, --part <N>   Only run part 1 or part 2
+  -a, --all        Run every day
+  -i, --input <PATH>
+                   Read the puzzle input from PATH instead of inputs/dayNN.txt,
+                   or from stdin if PATH is `-`. Needs a single day.
+      --answers <PATH>
+                   Check answers against PATH instead of answers.toml
+      --record     Save the computed answers to the answers file
+      --bench <N>  Run each day N times and report min/median/mean timings.
+                   Days are benchmarked one at a time, whatever --jobs says.
+      --format <FORMAT>
+                   `text` (default), or `json` for one object per line for each
+                   day and part with its answer, timing, input hash and status
+  -j, --jobs <N>   Run up to N days at once. Output stays in day order.
+  -h, --help       Print this message";
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Args {
+    pub days: Vec<usize>,
+    pub parts: Parts,
+    pub all: bool,
+    pub input: InputSource,
+    pub answers: PathBuf,
+    pub record: bool,
+    pub bench: Option<usize>,
+    pub format: Format,
+    pub jobs: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ArgsError {
+    Help,
+    Invalid(String),
+}
+
+fn parse_day(s: &str) -> Result<usize, ArgsError> {
+    match s.parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(ArgsError::Invalid(format!("invalid day `{s}`"))),
+    }
+}
+
+fn parse_days(s: &str, days: &mut Vec<usize>) -> Result<(), ArgsError> {
+    match s.split_once('-') {
+        Some((start, end)) => {
+            let (start, end) = (parse_day(start)?, parse_day(end)?);
+            if start > end {
+                return Err(ArgsError::Invalid(format!("empty day range `{s}`")));
+            }
+            days.extend(start..=end);
+        }
+        None => days.push(parse_day(s)?),
+    }
+    Ok(())
+}
+
+fn parse_part(s: &str) -> Result<Parts, ArgsError> {
+    match s {
+        "1" => Ok(Parts::One),
+        "2" => Ok(Parts::Two),
+        _ => Err(ArgsError::Invalid(format!(
+            "invalid part `{s}`, expected 1 or 2"
+        ))),
+    }
+}
+
+fn parse_count(s: &str, what: &str) -> Result<usize, ArgsError> {
+    match s.parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(ArgsError::Invalid(format!(
+            "invalid {what} `{s}`, expected a positive number"
+        ))),
+    }
+}
+
+fn parse_format(s: &str) -> Result<Format, ArgsError> {
+    match s {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(ArgsError::Invalid(format!(
+            "invalid format `{s}`, expected text or json"
+        ))),
+    }
+}
+
+fn parse_input(s: &str) -> InputSource {
+    match s {
+        "-" => InputSource::Stdin,
+        _ => InputSource::File(PathBuf::from(s)),
+    }
+}
+
+fn option_value<I>(option: &str, args: &mut I) -> Result<String, ArgsError>
+where
+    I: Iterator<Item = String>,
+{
+    args.next()
+        .ok_or_else(|| ArgsError::Invalid(format!("`{option}` needs a value")))
+}
+
+pub fn parse_args<I>(args: I) -> Result<Args, ArgsError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut days = Vec::new();
+    let mut parts = Parts::Both;
+    let mut all = false;
+    let mut input = InputSource::Default;
+    let mut answers = PathBuf::from(ANSWERS_PATH);
+    let mut record = false;
+    let mut bench = None;
+    let mut format = Format::Text;
+    let mut jobs = 1;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Err(ArgsError::Help),
+            "-a" | "--all" => all = true,
+            "-p" | "--part" => parts = parse_part(&option_value(&arg, &mut args)?)?,
+            "-i" | "--input" => input = parse_input(&option_value(&arg, &mut args)?),
+            "--answers" => answers = PathBuf::from(option_value(&arg, &mut args)?),
+            "--record" => record = true,
+            "--bench" => bench = Some(parse_count(&option_value(&arg, &mut args)?, "run count")?),
+            "--format" => format = parse_format(&option_value(&arg, &mut args)?)?,
+            "-j" | "--jobs" => jobs = parse_count(&option_value(&arg, &mut args)?, "job count")?,
+            _ => {
+                if let Some(value) = arg.strip_prefix("--part=") {
+                    parts = parse_part(value)?;
+                } else if let Some(value) = arg.strip_prefix("--input=") {
+                    input = parse_input(value);
+                } else if let Some(value) = arg.strip_prefix("--answers=") {
+                    answers = PathBuf::from(value);
+                } else if let Some(value) = arg.strip_prefix("--bench=") {
+                    bench = Some(parse_count(value, "run count")?);
+                } else if let Some(value) = arg.strip_prefix("--format=") {
+                    format = parse_format(value)?;
+                } else if let Some(value) = arg.strip_prefix("--jobs=") {
+                    jobs = parse_count(value, "job count")?;
+                } else if arg.starts_with('-') {
+                    return Err(ArgsError::Invalid(format!("unknown option `{arg}`")));
+                } else {
+                    parse_days(&arg, &mut days)?;
+                }
+            }
+        }
+    }
+
+    if all && !days.is_empty() {
+        return Err(ArgsError::Invalid(
+            "`--all` cannot be combined with a day list".to_string(),
+        ));
+    }
+    days.sort_unstable();
+    days.dedup();
+    if input != InputSource::Default && (all || days.len() > 1) {
+        return Err(ArgsError::Invalid(
+            "`--input` can only be used with a single day".to_string(),
+        ));
+    }
+
+    Ok(Args {
+        days,
+        parts,
+        all,
+        input,
+        answers,
+        record,
+        bench,
+        format,
+        jobs,
+    })
+}
+
+#[test]
+fn test_parse_args() {
+    let args = |s: &str| parse_args(s.split_whitespace().map(String::from));
+
+    assert_eq!(
+        args("1-5 8 3"),
+        Ok(Args {
+            days: vec![1, 2, 3, 4, 5, 8],
+            parts: Parts::Both,
+            all: false,
+            input: InputSource::Default,
+            answers: PathBuf::from(ANSWERS_PATH),
+            record: false,
+            bench: None,
+            format: Format::Text,
+            jobs: 1,
+        })
+    );
+    assert_eq!(
+        args("--all --part 2"),
+        Ok(Args {
+            days: vec![],
+            parts: Parts::Two,
+            all: true,
+            input: InputSource::Default,
+            answers: PathBuf::from(ANSWERS_PATH),
+            record: false,
+            bench: None,
+            format: Format::Text,
+            jobs: 1,
+        })
+    );
+    assert_eq!(args("4 --part=1").unwrap().parts, Parts::One);
+    assert!(matches!(args("5-3"), Err(ArgsError::Invalid(_))));
+    assert!(matches!(args("0"), Err(ArgsError::Invalid(_))));
+    assert!(matches!(args("1 --part 3"), Err(ArgsError::Invalid(_))));
+    assert!(matches!(args("--all 2"), Err(ArgsError::Invalid(_))));
+    assert_eq!(args("-h"), Err(ArgsError::Help));
+
+    assert_eq!(args("3 --input -").unwrap().input, InputSource::Stdin);
+    assert_eq!(
+        args("3 -i my/day3.txt").unwrap().input,
+        InputSource::File(PathBuf::from("my/day3.txt"))
+    );
+    assert!(matches!(args("1-2 -i -"), Err(ArgsError::Invalid(_))));
+
+    let with_answers = args("--all --record --answers mine.toml").unwrap();
+    assert!(with_answers.record);
+    assert_eq!(with_answers.answers, PathBuf::from("mine.toml"));
+    assert_eq!(args("1 --bench 20").unwrap().bench, Some(20));
+    assert!(matches!(args("1 --bench 0"), Err(ArgsError::Invalid(_))));
+    assert_eq!(args("--all --format json").unwrap().format, Format::Json);
+    assert!(matches!(args("--format xml"), Err(ArgsError::Invalid(_))));
+    assert_eq!(args("--all -j 4").unwrap().jobs, 4);
+    assert!(matches!(args("--all --jobs 0"), Err(ArgsError::Invalid(_))));
+}
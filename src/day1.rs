@@ -1,4 +1,10 @@
-use std::ascii;
+use std::cmp::Reverse;
+use std::io::{self, BufRead};
+use std::sync::OnceLock;
+
+use crate::aho_corasick::AhoCorasick;
+use crate::error::parse_lines;
+use crate::{ParseError, Solution};
 
 struct AsciiDictNode {
     v: Option<u8>,
@@ -15,17 +21,18 @@ impl Default for AsciiDictNode {
     }
 }
 
+/// A trie of words and their reverses.
 #[derive(Default)]
-struct AsciiDict {
+pub struct AsciiDict {
     node: AsciiDictNode,
 }
 
 impl AsciiDict {
-    fn add(&mut self, s: &str, v: u8) -> () {
-        fn go(ac: &[ascii::Char], v: u8, n: &mut AsciiDictNode) -> () {
+    pub fn add(&mut self, s: &str, v: u8) {
+        fn go(ac: &[u8], v: u8, n: &mut AsciiDictNode) {
             match ac.split_first() {
                 Some((c, cs)) => {
-                    let idx = c.to_u8() as usize;
+                    let idx = *c as usize;
                     match n.nodes[idx] {
                         Some(ref mut n2) => {
                             go(cs, v, n2.as_mut());
@@ -43,36 +50,24 @@ impl AsciiDict {
             }
         }
 
-        go(
-            s.as_ascii()
-                .expect("Tried to add non-ASCII string to AsciiDict"),
-            v,
-            &mut self.node,
-        );
+        assert!(s.is_ascii(), "Tried to add non-ASCII string to AsciiDict");
+        go(s.as_bytes(), v, &mut self.node);
         // add the reverse as well
-        go(
-            s.chars()
-                .rev()
-                .collect::<String>()
-                .as_ascii()
-                .expect("Tried to add non-ASCII string to AsciiDict"),
-            v,
-            &mut self.node,
-        );
+        let reversed: Vec<u8> = s.bytes().rev().collect();
+        go(&reversed, v, &mut self.node);
     }
 
-    fn get_value_part2(&self, cs: &str) -> Option<u8> {
+    /// Value of the first word found, trying each offset of `cs` in turn.
+    pub fn get_value_part2(&self, cs: &str) -> Option<u8> {
         fn go<I>(node: &AsciiDictNode, cs2: &mut I) -> Option<u8>
         where
-            I: Iterator<Item = char>,
+            I: Iterator<Item = u8>,
         {
             match node.v {
                 Some(v) => Some(v),
                 None => match cs2.next() {
-                    Some(c) => match &node.nodes[c.as_ascii().unwrap().to_u8() as usize] {
-                        Some(inner_node) => {
-                            return go(&inner_node, cs2);
-                        }
+                    Some(c) => match node.nodes.get(c as usize).and_then(Option::as_ref) {
+                        Some(inner_node) => go(inner_node, cs2),
                         None => None,
                     },
                     None => None,
@@ -80,7 +75,7 @@ impl AsciiDict {
             }
         }
 
-        let mut chars = cs.chars().peekable();
+        let mut chars = cs.bytes().peekable();
         //go(&self.node, &mut chars);
         while chars.peek().is_some() {
             match go(&self.node, &mut chars.clone()) {
@@ -96,59 +91,567 @@ impl AsciiDict {
     }
 }
 
-fn get_value_part2(s: &str) -> usize {
+/// The digit words from the puzzle.
+pub const ENGLISH: [(&str, u8); 10] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+pub const GERMAN: [(&str, u8); 10] = [
+    ("null", 0),
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+
+pub const ROMAN: [(&str, u8); 9] = [
+    ("I", 1),
+    ("II", 2),
+    ("III", 3),
+    ("IV", 4),
+    ("V", 5),
+    ("VI", 6),
+    ("VII", 7),
+    ("VIII", 8),
+    ("IX", 9),
+];
+
+pub const NUMERALS: [(&str, u8); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+/// The original part 2: a trie walk from every offset, forwards and then over the reversed
+/// line. Kept to benchmark against [`get_value_part2`].
+pub fn get_value_part2_trie(s: &str) -> usize {
     let mut dict = AsciiDict::default();
-    dict.add("zero", 0);
-    dict.add("one", 1);
-    dict.add("two", 2);
-    dict.add("three", 3);
-    dict.add("four", 4);
-    dict.add("five", 5);
-    dict.add("six", 6);
-    dict.add("seven", 7);
-    dict.add("eight", 8);
-    dict.add("nine", 9);
-    dict.add("0", 0);
-    dict.add("1", 1);
-    dict.add("2", 2);
-    dict.add("3", 3);
-    dict.add("4", 4);
-    dict.add("5", 5);
-    dict.add("6", 6);
-    dict.add("7", 7);
-    dict.add("8", 8);
-    dict.add("9", 9);
+    for (word, v) in ENGLISH.into_iter().chain(NUMERALS) {
+        dict.add(word, v);
+    }
 
-    let first = dict.get_value_part2(&s).unwrap();
+    let first = dict.get_value_part2(s).unwrap();
 
     let reversed = s.chars().rev().collect::<String>();
     let last = dict.get_value_part2(&reversed).unwrap();
     (first * 10 + last) as usize
 }
 
-fn get_value_part1(s: &str) -> usize {
-    let mut first = 0;
-    let mut last = 0;
-    for c in s.as_ascii().unwrap().into_iter() {
-        if let Some(x) = c.to_char().to_digit(10) {
-            if first == 0 {
-                first = x as usize;
+/// Whether a digit was written out as a word or as a numeral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Form {
+    Spelled,
+    Numeric,
+}
+
+/// A digit found in a calibration line, or with [`number_matches`] a number that may span
+/// several words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigitMatch {
+    /// Byte offset and length of the match in the line.
+    pub offset: usize,
+    pub len: usize,
+    pub value: u32,
+    pub form: Form,
+}
+
+impl DigitMatch {
+    pub fn end(&self) -> usize {
+        self.offset + self.len
+    }
+}
+
+/// The words and numerals that stand for digits, and the automaton that finds them. Build
+/// one once and share it across lines.
+#[derive(Debug)]
+pub struct Vocabulary {
+    words: Vec<(String, u8)>,
+    automaton: AhoCorasick,
+}
+
+impl Vocabulary {
+    /// Words may be any non-empty text, and values must be digits from 0 to 9. A word
+    /// made only of ASCII digits is [`Form::Numeric`], anything else is [`Form::Spelled`].
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = (S, u8)>,
+        S: Into<String>,
+    {
+        let words: Vec<(String, u8)> = words.into_iter().map(|(w, v)| (w.into(), v)).collect();
+        if let Some((word, value)) = words.iter().find(|(_, v)| *v > 9) {
+            panic!("{word:?} stands for {value}, which is not a digit");
+        }
+        let automaton = AhoCorasick::new(words.iter().map(|(w, _)| w));
+        Vocabulary { words, automaton }
+    }
+
+    /// One `word digit` pair per line. Blank lines and lines starting with `#` are skipped.
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let words = parse_lines(text, |line| {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                return Ok(None);
+            }
+            let mut tokens = line.split_whitespace();
+            let word = tokens.next().unwrap();
+            let Some(value) = tokens.next() else {
+                return Err(ParseError::at_end(line, "a digit"));
+            };
+            let digit = match value.parse::<u8>() {
+                Ok(digit) if digit <= 9 => digit,
+                _ => return Err(ParseError::at(line, value, "a digit from 0 to 9")),
+            };
+            if let Some(extra) = tokens.next() {
+                return Err(ParseError::at(line, extra, "end of line"));
+            }
+            Ok(Some((word.to_string(), digit)))
+        })?;
+        Ok(Self::new(words.into_iter().flatten()))
+    }
+
+    /// The puzzle's vocabulary: [`ENGLISH`] words and [`NUMERALS`].
+    pub fn english() -> &'static Vocabulary {
+        static ENGLISH_VOCABULARY: OnceLock<Vocabulary> = OnceLock::new();
+        ENGLISH_VOCABULARY.get_or_init(|| Vocabulary::new(ENGLISH.into_iter().chain(NUMERALS)))
+    }
+
+    /// Every digit in `line`, ordered by offset. Words that share letters, like "twone",
+    /// each give a match.
+    pub fn matches(&self, line: &str) -> Vec<DigitMatch> {
+        let mut matches: Vec<_> = self
+            .automaton
+            .find_iter(line.as_bytes())
+            .map(|m| {
+                let (word, value) = &self.words[m.pattern];
+                let form = if word.bytes().all(|b| b.is_ascii_digit()) {
+                    Form::Numeric
+                } else {
+                    Form::Spelled
+                };
+                DigitMatch {
+                    offset: m.start,
+                    len: m.end - m.start,
+                    value: (*value).into(),
+                    form,
+                }
+            })
+            .collect();
+        matches.sort_by_key(|m| m.offset);
+        matches
+    }
+}
+
+/// Every digit in `line` using the puzzle's vocabulary. See [`Vocabulary::matches`].
+pub fn digit_matches(line: &str) -> Vec<DigitMatch> {
+    Vocabulary::english().matches(line)
+}
+
+/// The first digit is the match that starts earliest and the last is the one that ends
+/// latest, the longer match winning a tie. `None` if there are no matches.
+pub fn calibration_value(matches: &[DigitMatch]) -> Option<usize> {
+    let first = matches.iter().min_by_key(|m| (m.offset, Reverse(m.len)))?;
+    let last = matches.iter().max_by_key(|m| (m.end(), m.len))?;
+    Some(first.value as usize * 10 + last.value as usize)
+}
+
+/// Part 2's value for a line, counting spelled digits. Lines without digits are worth 0.
+pub fn get_value_part2(s: &str) -> usize {
+    calibration_value(&digit_matches(s)).unwrap_or(0)
+}
+
+/// Words for [`number_matches`], beyond the single digits.
+const NUMBER_WORDS: [(&str, u32); 29] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+    ("ten", 10),
+    ("eleven", 11),
+    ("twelve", 12),
+    ("thirteen", 13),
+    ("fourteen", 14),
+    ("fifteen", 15),
+    ("sixteen", 16),
+    ("seventeen", 17),
+    ("eighteen", 18),
+    ("nineteen", 19),
+    ("twenty", 20),
+    ("thirty", 30),
+    ("forty", 40),
+    ("fifty", 50),
+    ("sixty", 60),
+    ("seventy", 70),
+    ("eighty", 80),
+    ("ninety", 90),
+    ("hundred", 100),
+];
+
+fn number_words() -> &'static AhoCorasick {
+    static NUMBER_WORDS_AC: OnceLock<AhoCorasick> = OnceLock::new();
+    NUMBER_WORDS_AC.get_or_init(|| AhoCorasick::new(NUMBER_WORDS.map(|(word, _)| word)))
+}
+
+/// Where a spelled number is up to, which decides the words that may follow.
+#[derive(Debug, Clone, Copy)]
+enum Phase {
+    Start,
+    /// A unit, which "hundred" can multiply.
+    Unit,
+    /// A multiple of ten, which a unit can complete.
+    Tens,
+    /// Hundreds, which a unit, teen or tens word can add to.
+    Hundred,
+    Done,
+}
+
+fn next_phase(phase: Phase, value: u32, word: u32) -> Option<(Phase, u32)> {
+    Some(match (phase, word) {
+        (Phase::Start, 0 | 10..=19) => (Phase::Done, word),
+        (Phase::Start, 1..=9) => (Phase::Unit, word),
+        (Phase::Start, 20..=90) => (Phase::Tens, word),
+        (Phase::Start | Phase::Unit, 100) => (Phase::Hundred, value.max(1) * 100),
+        (Phase::Tens, 1..=9) | (Phase::Hundred, 1..=19) => (Phase::Done, value + word),
+        (Phase::Hundred, 20..=90) => (Phase::Tens, value + word),
+        _ => return None,
+    })
+}
+
+/// Every number in `line`, ordered by offset: each run of number words that reads as a
+/// number below 1000, such as "twentyone", "eleven" or "onehundredfive", and each numeral
+/// as a single digit. Every parse is returned, so "twentyone" also gives "twenty" and
+/// "one", and words that share letters each give a match.
+pub fn number_matches(line: &str) -> Vec<DigitMatch> {
+    let mut words_at = vec![Vec::new(); line.len()];
+    for m in number_words().find_iter(line.as_bytes()) {
+        words_at[m.start].push((m.end, NUMBER_WORDS[m.pattern].1));
+    }
+
+    let mut matches = Vec::new();
+    for (offset, b) in line.bytes().enumerate() {
+        if b.is_ascii_digit() {
+            let value = (b - b'0') as u32;
+            matches.push(DigitMatch {
+                offset,
+                len: 1,
+                value,
+                form: Form::Numeric,
+            });
+        }
+        let mut stack = vec![(offset, Phase::Start, 0)];
+        while let Some((at, phase, value)) = stack.pop() {
+            for &(end, word) in words_at.get(at).into_iter().flatten() {
+                if let Some((phase, value)) = next_phase(phase, value, word) {
+                    matches.push(DigitMatch {
+                        offset,
+                        len: end - offset,
+                        value,
+                        form: Form::Spelled,
+                    });
+                    stack.push((end, phase, value));
+                }
             }
-            last = x as usize;
         }
     }
-    return first * 10 + last;
+    matches.sort_by_key(|m| (m.offset, m.len));
+    matches
+}
+
+/// The calibration value with compound numbers: the first and last numbers written one
+/// after the other, so "twentyone" gives 2121 and "eleven3" gives 113.
+///
+/// Parses overlap the same way digits do in part 2. The first number is the longest one
+/// starting earliest and the last is the longest one ending latest, so "twentyone" reads
+/// as 21 both ways while "twone" is still 2 then 1. `None` if there are no numbers.
+pub fn compound_value(line: &str) -> Option<u64> {
+    let matches = number_matches(line);
+    let first = matches.iter().min_by_key(|m| (m.offset, Reverse(m.len)))?;
+    let last = matches.iter().max_by_key(|m| (m.end(), m.len))?;
+    let shift = 10u64.pow(last.value.checked_ilog10().unwrap_or(0) + 1);
+    Some(first.value as u64 * shift + last.value as u64)
+}
+
+/// Part 1's value for a line, from numerals only. `None` if the line has none.
+fn get_value_part1(s: &str) -> Option<usize> {
+    let mut first = None;
+    let mut last = 0;
+    for c in s.chars() {
+        if let Some(x) = c.to_digit(10) {
+            first.get_or_insert(x);
+            last = x;
+        }
+    }
+    first.map(|first| (first * 10 + last) as usize)
+}
+
+/// One calibration line's values, or `None` for a part when the line has no digits that
+/// part counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineValues {
+    /// 1-based line number.
+    pub line: usize,
+    pub part1: Option<usize>,
+    pub part2: Option<usize>,
+}
+
+/// Reads a calibration document a line at a time, keeping running totals for both parts,
+/// so piped or very large input is handled in constant memory.
+pub struct CalibrationReader<R> {
+    reader: R,
+    buf: String,
+    line: usize,
+    totals: [usize; 2],
+}
+
+impl<R: BufRead> CalibrationReader<R> {
+    pub fn new(reader: R) -> Self {
+        CalibrationReader {
+            reader,
+            buf: String::new(),
+            line: 0,
+            totals: [0, 0],
+        }
+    }
+
+    /// Sums for part 1 and part 2 over the lines read so far.
+    pub fn totals(&self) -> [usize; 2] {
+        self.totals
+    }
+}
+
+impl<R: BufRead> Iterator for CalibrationReader<R> {
+    type Item = io::Result<LineValues>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buf.clear();
+        match self.reader.read_line(&mut self.buf) {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(err) => return Some(Err(err)),
+        }
+        self.line += 1;
+        let text = self.buf.trim_end_matches(['\n', '\r']);
+        let part1 = get_value_part1(text);
+        let part2 = calibration_value(&digit_matches(text));
+        self.totals[0] += part1.unwrap_or(0);
+        self.totals[1] += part2.unwrap_or(0);
+        Some(Ok(LineValues {
+            line: self.line,
+            part1,
+            part2,
+        }))
+    }
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: usize = 1;
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part1(lines: &Self::Input) -> usize {
+        lines
+            .iter()
+            .map(|line| get_value_part1(line).unwrap_or(0))
+            .sum()
+    }
+
+    fn part2(lines: &Self::Input) -> usize {
+        lines.iter().map(|line| get_value_part2(line)).sum()
+    }
+}
+
+#[test]
+fn test_example() {
+    assert_eq!(Day1::part1(&Day1::parse(EXAMPLE).unwrap()), 142);
+    assert_eq!(Day1::part2(&Day1::parse(EXAMPLE2).unwrap()), 281);
+}
+
+#[test]
+fn test_overlapping_words() {
+    // Words that share letters each count, so the last digit comes from the second word
+    assert_eq!(get_value_part2("twone"), 21);
+    assert_eq!(get_value_part2("oneight"), 18);
+    assert_eq!(get_value_part2("eightwo"), 82);
+    assert_eq!(get_value_part2("xtwone3four"), 24);
+    assert_eq!(get_value_part2("zoneight234"), 14);
+    assert_eq!(get_value_part2("5sevenineight"), 58);
+    assert_eq!(get_value_part2("7"), 77);
+}
+
+#[test]
+fn test_trie_agrees() {
+    for line in Day1::parse(INPUT).unwrap() {
+        assert_eq!(
+            get_value_part2(&line),
+            get_value_part2_trie(&line),
+            "{line}"
+        );
+    }
+}
+
+#[test]
+fn test_digit_matches() {
+    let spelled = |offset, len, value| DigitMatch {
+        offset,
+        len,
+        value,
+        form: Form::Spelled,
+    };
+    assert_eq!(
+        digit_matches("twone3"),
+        [
+            spelled(0, 3, 2),
+            spelled(2, 3, 1),
+            DigitMatch {
+                offset: 5,
+                len: 1,
+                value: 3,
+                form: Form::Numeric
+            }
+        ]
+    );
+    assert_eq!(
+        digit_matches("xoneightx"),
+        [spelled(1, 3, 1), spelled(3, 5, 8)]
+    );
+    assert_eq!(digit_matches("abc"), []);
+    assert_eq!(calibration_value(&[]), None);
 }
 
-pub fn solve() {
-    println!("Day 1");
-    let part_1_result: usize = INPUT.lines().map(|line| get_value_part1(line)).sum();
-    println!("Part 1: {part_1_result}");
+#[test]
+fn test_vocabularies() {
+    let german = Vocabulary::new(GERMAN);
+    let values: Vec<_> = german
+        .matches("xfünfzweineun")
+        .iter()
+        .map(|m| m.value)
+        .collect();
+    assert_eq!(values, [5, 2, 9]);
+    assert_eq!(calibration_value(&german.matches("achtundsechs")), Some(86));
+
+    // The longest numeral wins where several start or end at the same place
+    let roman = Vocabulary::new(ROMAN);
+    assert_eq!(calibration_value(&roman.matches("xVIIIyIXz")), Some(89));
+    assert_eq!(calibration_value(&roman.matches("IV")), Some(44));
+
+    let custom = Vocabulary::parse("# Dutch\ntwee 2\n\ndrie 3\n").unwrap();
+    assert_eq!(calibration_value(&custom.matches("tweedrie")), Some(23));
+    let err = Vocabulary::parse("een 1\nelf 11").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 5));
+    let err = Vocabulary::parse("een 1\n   elf 11").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 8));
+    assert!(Vocabulary::parse("een").is_err());
+}
+
+#[test]
+#[should_panic(expected = "not a digit")]
+fn test_vocabulary_rejects_numbers() {
+    Vocabulary::new([("thirty", 30)]);
+}
+
+#[test]
+fn test_compound_numbers() {
+    assert_eq!(compound_value("twentyone"), Some(2121));
+    assert_eq!(compound_value("xeleven3"), Some(113));
+    assert_eq!(compound_value("onehundredtwentythree"), Some(123123));
+    assert_eq!(compound_value("hundred"), Some(100100));
+    assert_eq!(compound_value("ninehundredfifteenx0"), Some(9150));
+    assert_eq!(compound_value("eighteen"), Some(1818));
+    assert_eq!(compound_value("seventyeightwo"), Some(782));
+    assert_eq!(compound_value("twone"), Some(21));
+    assert_eq!(compound_value("4nineeightseven2"), Some(42));
+    assert_eq!(compound_value("abc"), None);
+
+    let values: Vec<_> = number_matches("sixty5")
+        .iter()
+        .map(|m| (m.value, m.form))
+        .collect();
+    assert_eq!(
+        values,
+        [(6, Form::Spelled), (60, Form::Spelled), (5, Form::Numeric)]
+    );
+}
+
+#[test]
+fn test_calibration_reader() {
+    let text = "1abc2\r\nxtwone\nnothing\n7pqrstsixteen\n0a5";
+    let mut reader = CalibrationReader::new(text.as_bytes());
+    let values: Vec<_> = reader.by_ref().map(Result::unwrap).collect();
+    let parts: Vec<_> = values.iter().map(|v| (v.line, v.part1, v.part2)).collect();
+    assert_eq!(
+        parts,
+        [
+            (1, Some(12), Some(12)),
+            (2, None, Some(21)),
+            (3, None, None),
+            (4, Some(77), Some(76)),
+            // A leading 0 is still the first digit
+            (5, Some(5), Some(5)),
+        ]
+    );
+    assert_eq!(reader.totals(), [94, 114]);
+
+    let mut reader = CalibrationReader::new(INPUT.as_bytes());
+    reader.by_ref().for_each(|v| assert!(v.is_ok()));
+    assert_eq!(reader.totals(), [55172, 54925]);
+}
+
+#[cfg(test)]
+static EXAMPLE: &str = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+
+#[cfg(test)]
+static EXAMPLE2: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
 
-    let part_2_result: usize = INPUT.lines().map(|line| get_value_part2(line)).sum();
-    println!("Part 2: {part_2_result}");
+#[test]
+fn test_input() {
+    let lines = Day1::parse(INPUT).unwrap();
+    assert_eq!(Day1::part1(&lines), 55172);
+    assert_eq!(Day1::part2(&lines), 54925);
 }
 
+#[cfg(test)]
 static INPUT: &str = "qzjggk1one
 two2seven7
 vszthreetwo6threethree4two3
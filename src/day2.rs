@@ -1,71 +1,668 @@
-#[derive(Debug)]
-struct Game {
-    n: usize,
-    sets: Vec<(usize, usize, usize)>,
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write};
+
+use crate::error::{parse_lines, parse_number};
+use crate::json::{self, Object};
+use crate::rng::Rng;
+use crate::{ParseError, Solution};
+
+/// One handful of cubes: how many of each colour were shown, in the order they were
+/// written.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Draw(Vec<(String, usize)>);
+
+impl Draw {
+    pub fn new() -> Self {
+        Draw(Vec::new())
+    }
+
+    pub fn get(&self, colour: &str) -> Option<usize> {
+        self.0.iter().find(|(c, _)| c == colour).map(|&(_, n)| n)
+    }
+
+    /// Sets the count for `colour`, returning the old one. A new colour goes last, and a
+    /// colour already in the draw keeps its place.
+    pub fn insert(&mut self, colour: String, n: usize) -> Option<usize> {
+        match self.0.iter_mut().find(|(c, _)| *c == colour) {
+            Some((_, old)) => Some(std::mem::replace(old, n)),
+            None => {
+                self.0.push((colour, n));
+                None
+            }
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.0.iter().map(|(c, n)| (c.as_str(), *n))
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|(c, _)| c.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
 
-fn line_to_game(line: &str) -> Game {
-    let mut game_val_it = line.split(':');
-    let mut game_number_it = game_val_it.next().unwrap().split_ascii_whitespace();
-    let game_number = game_number_it.nth(1).unwrap().parse::<usize>().unwrap();
-    let sets = game_val_it.next().unwrap();
+impl FromIterator<(String, usize)> for Draw {
+    fn from_iter<I: IntoIterator<Item = (String, usize)>>(iter: I) -> Self {
+        let mut draw = Draw::new();
+        for (colour, n) in iter {
+            draw.insert(colour, n);
+        }
+        draw
+    }
+}
 
-    let sets: Vec<_> = sets.split(';').map(|s| set_to_colors(s)).collect();
-    Game {
-        n: game_number,
-        sets: sets,
+/// A game as the puzzle writes it. Parsed games always have at least one draw and no
+/// empty draws. A game built by hand needs the same to be written out and read back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub n: usize,
+    pub sets: Vec<Draw>,
+}
+
+/// Writes the game in the puzzle's format, with colours in the order they were written.
+/// An empty draw writes nothing, which [`parse_games`] rejects.
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Game {}:", self.n)?;
+        for (i, set) in self.sets.iter().enumerate() {
+            f.write_str(if i == 0 { " " } else { "; " })?;
+            for (j, (colour, n)) in set.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{n} {colour}")?;
+            }
+        }
+        Ok(())
     }
 }
 
-fn set_to_colors(set: &str) -> (usize, usize, usize) {
-    let mut red = 0;
-    let mut green = 0;
-    let mut blue = 0;
+/// A game with ID `n`: one to six draws, each showing between 0 and 20 cubes of at least
+/// one of `colours`, which must not be empty.
+pub fn random_game(rng: &mut Rng, n: usize, colours: &[&str]) -> Game {
+    assert!(!colours.is_empty(), "a game needs at least one colour");
+    let sets = (0..1 + rng.below(6))
+        .map(|_| {
+            let mut set = Draw::new();
+            while set.is_empty() {
+                for colour in colours {
+                    if rng.percent(50) {
+                        set.insert(colour.to_string(), rng.below(21));
+                    }
+                }
+            }
+            set
+        })
+        .collect();
+    Game { n, sets }
+}
+
+/// A record of games and the colours of cube they are played with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Games {
+    pub colours: BTreeSet<String>,
+    pub games: Vec<Game>,
+}
+
+/// One game per line, as [`parse_games`] reads them.
+impl fmt::Display for Games {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for game in &self.games {
+            writeln!(f, "{game}")?;
+        }
+        Ok(())
+    }
+}
 
-    let x = set.split(',');
-    for color in x {
+/// Parses one game per line. With `colours`, any other colour is an error. Without, the
+/// colours are whichever ones the games use.
+pub fn parse_games(input: &str, colours: Option<&BTreeSet<String>>) -> Result<Games, ParseError> {
+    let games = parse_lines(input, |line| line_to_game(line, colours))?;
+    let colours = match colours {
+        Some(colours) => colours.clone(),
+        None => games
+            .iter()
+            .flat_map(|game| {
+                game.sets
+                    .iter()
+                    .flat_map(|set| set.colours().map(String::from))
+            })
+            .collect(),
+    };
+    Ok(Games { colours, games })
+}
+
+fn line_to_game(line: &str, colours: Option<&BTreeSet<String>>) -> Result<Game, ParseError> {
+    let (game_s, sets) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::at_end(line, "`:`"))?;
+    let game_number = game_s
+        .strip_prefix("Game ")
+        .ok_or_else(|| ParseError::at(line, game_s, "`Game <id>`"))?;
+    let game_number = parse_number(line, game_number.trim())?;
+
+    let sets = sets
+        .split(';')
+        .map(|set| set_to_colors(line, set, colours))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Game {
+        n: game_number,
+        sets,
+    })
+}
+
+fn set_to_colors(
+    line: &str,
+    set: &str,
+    colours: Option<&BTreeSet<String>>,
+) -> Result<Draw, ParseError> {
+    let mut draw = Draw::new();
+    for color in set.split(',') {
         let mut it = color.split_ascii_whitespace();
-        let ct: usize = it.next().unwrap().parse().unwrap();
-        match it.next().unwrap() {
-            "red" => red = ct,
-            "green" => green = ct,
-            "blue" => blue = ct,
-            _ => (),
+        let ct: usize = match it.next() {
+            Some(ct) => parse_number(line, ct)?,
+            None => return Err(ParseError::at(line, color, "a cube count")),
+        };
+        let name = match it.next() {
+            Some(name) => name,
+            None => return Err(ParseError::at_end(color, "a colour").within(line, color)),
+        };
+        if let Some(colours) = colours.filter(|c| !c.contains(name)) {
+            let names: Vec<_> = colours.iter().map(String::as_str).collect();
+            let expected = format!("one of the colours {}", names.join(", "));
+            return Err(ParseError::at(line, name, expected));
+        }
+        if draw.insert(name.to_string(), ct).is_some() {
+            return Err(ParseError::at(
+                line,
+                name,
+                "a colour not already in this draw",
+            ));
+        }
+    }
+    Ok(draw)
+}
+
+/// How many `colour` cubes were shown in `set`.
+fn count(set: &Draw, colour: &str) -> usize {
+    set.get(colour).unwrap_or(0)
+}
+
+/// The bag from part 1. It holds no cubes of any other colour.
+pub fn puzzle_bag() -> Draw {
+    [("red", 12), ("green", 13), ("blue", 14)]
+        .into_iter()
+        .map(|(colour, n)| (colour.to_string(), n))
+        .collect()
+}
+
+/// Parses a bag written like a draw, such as `12 red, 13 green, 14 blue`.
+pub fn parse_bag(text: &str) -> Result<Draw, ParseError> {
+    set_to_colors(text, text, None)
+}
+
+/// The first time a game shows more cubes of a colour than the bag holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// 1-based index of the draw within the game.
+    pub draw: usize,
+    pub colour: String,
+    pub shown: usize,
+    pub limit: usize,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "draw {} shows {} {} but the bag holds {}",
+            self.draw, self.shown, self.colour, self.limit
+        )
+    }
+}
+
+impl Game {
+    /// Whether the game could have been played with `bag`. Draws are checked in order, and
+    /// colours within a draw in the order they were written.
+    pub fn check(&self, bag: &Draw) -> Result<(), Violation> {
+        for (i, set) in self.sets.iter().enumerate() {
+            for (colour, shown) in set.iter() {
+                let limit = count(bag, colour);
+                if shown > limit {
+                    return Err(Violation {
+                        draw: i + 1,
+                        colour: colour.to_string(),
+                        shown,
+                        limit,
+                    });
+                }
+            }
         }
+        Ok(())
     }
+}
+
+/// Which games could have been played with a bag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feasibility {
+    /// IDs of the games that fit.
+    pub feasible: Vec<usize>,
+    /// IDs of the games that don't, with the first draw that rules each out.
+    pub infeasible: Vec<(usize, Violation)>,
+}
 
-    (red, green, blue)
+pub fn feasible_games(games: &[Game], bag: &Draw) -> Feasibility {
+    let mut feasible = Vec::new();
+    let mut infeasible = Vec::new();
+    for game in games {
+        match game.check(bag) {
+            Ok(()) => feasible.push(game.n),
+            Err(violation) => infeasible.push((game.n, violation)),
+        }
+    }
+    Feasibility {
+        feasible,
+        infeasible,
+    }
 }
 
-fn power(game: &Game) -> usize {
-    let (r, g, b) = game.sets.iter().fold((0, 0, 0), |acc, set| {
-        (set.0.max(acc.0), set.1.max(acc.1), set.2.max(acc.2))
-    });
-    r * g * b
+/// The fewest cubes of one colour a game could have been played with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Minimum {
+    pub count: usize,
+    /// 1-based index of the first draw showing `count` cubes, or `None` if the game never
+    /// shows the colour.
+    pub draw: Option<usize>,
 }
 
-fn part_1_possible(game: &Game) -> bool {
-    game.sets.iter().all(|set| part_1_possible_set(set))
+/// The smallest bag a game could have been played with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinimumBag {
+    pub game: usize,
+    pub colours: BTreeMap<String, Minimum>,
 }
 
-fn part_1_possible_set(&set: &(usize, usize, usize)) -> bool {
-    set.0 <= 12 && set.1 <= 13 && set.2 <= 14
+impl MinimumBag {
+    /// The product of the minimum counts. A colour the game never shows makes it 0.
+    pub fn power(&self) -> usize {
+        self.colours.values().map(|m| m.count).product()
+    }
+}
+
+impl Game {
+    pub fn minimum_bag(&self, colours: &BTreeSet<String>) -> MinimumBag {
+        let minimum = |colour: &str| {
+            let mut minimum = Minimum {
+                count: 0,
+                draw: None,
+            };
+            for (i, set) in self.sets.iter().enumerate() {
+                match set.get(colour) {
+                    Some(n) if minimum.draw.is_none() || n > minimum.count => {
+                        minimum = Minimum {
+                            count: n,
+                            draw: Some(i + 1),
+                        };
+                    }
+                    _ => {}
+                }
+            }
+            minimum
+        };
+        MinimumBag {
+            game: self.n,
+            colours: colours.iter().map(|c| (c.clone(), minimum(c))).collect(),
+        }
+    }
+}
+
+/// How much of one colour the games needed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColourStats {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+    /// How many games needed each count.
+    pub histogram: BTreeMap<usize, usize>,
+    /// How many games needed more of this colour than any other, ties included.
+    pub largest_in: usize,
+}
+
+/// The minimum bag of every game, with statistics across them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BagReport {
+    pub bags: Vec<MinimumBag>,
+    pub stats: BTreeMap<String, ColourStats>,
+}
+
+impl BagReport {
+    pub fn new(games: &Games) -> Self {
+        let bags: Vec<_> = games
+            .games
+            .iter()
+            .map(|game| game.minimum_bag(&games.colours))
+            .collect();
+        let stats = games
+            .colours
+            .iter()
+            .map(|colour| {
+                let counts: Vec<usize> = bags.iter().map(|b| b.colours[colour].count).collect();
+                let mut histogram = BTreeMap::new();
+                for &n in &counts {
+                    *histogram.entry(n).or_insert(0) += 1;
+                }
+                let largest_in = bags
+                    .iter()
+                    .filter(|bag| {
+                        let largest = bag.colours.values().map(|m| m.count).max();
+                        Some(bag.colours[colour].count) == largest
+                    })
+                    .count();
+                let stats = ColourStats {
+                    min: counts.iter().copied().min().unwrap_or(0),
+                    max: counts.iter().copied().max().unwrap_or(0),
+                    mean: counts.iter().sum::<usize>() as f64 / counts.len().max(1) as f64,
+                    histogram,
+                    largest_in,
+                };
+                (colour.clone(), stats)
+            })
+            .collect();
+        BagReport { bags, stats }
+    }
+
+    /// The colour that most often needs the most cubes, the first alphabetically on a tie.
+    pub fn most_constraining(&self) -> Option<&str> {
+        let (colour, _) = self
+            .stats
+            .iter()
+            .min_by_key(|(colour, stats)| (Reverse(stats.largest_in), *colour))?;
+        Some(colour)
+    }
+
+    /// One row per game with each colour's minimum and the draw that forced it, then a row
+    /// per colour of statistics.
+    pub fn table(&self) -> String {
+        let colours: Vec<&String> = self.stats.keys().collect();
+        let mut table = format!("{:>5}", "Game");
+        for colour in &colours {
+            write!(table, "  {colour:>8}").unwrap();
+        }
+        writeln!(table, "  {:>10}", "Power").unwrap();
+        for bag in &self.bags {
+            write!(table, "{:>5}", bag.game).unwrap();
+            for colour in &colours {
+                let minimum = bag.colours[*colour];
+                let draw = minimum.draw.map_or("-".to_string(), |d| format!("@{d}"));
+                write!(table, "  {:>8}", format!("{} {draw:<3}", minimum.count)).unwrap();
+            }
+            writeln!(table, "  {:>10}", bag.power()).unwrap();
+        }
+
+        writeln!(
+            table,
+            "\n{:<8}  {:>5}  {:>7}  {:>5}  {:>10}",
+            "Colour", "Min", "Mean", "Max", "Largest in"
+        )
+        .unwrap();
+        for (colour, stats) in &self.stats {
+            writeln!(
+                table,
+                "{colour:<8}  {:>5}  {:>7.2}  {:>5}  {:>10}",
+                stats.min, stats.mean, stats.max, stats.largest_in
+            )
+            .unwrap();
+        }
+        if let Some(colour) = self.most_constraining() {
+            writeln!(table, "Most constraining: {colour}").unwrap();
+        }
+        table
+    }
+
+    pub fn to_json(&self) -> String {
+        let bags = self.bags.iter().map(|bag| {
+            let colours = bag
+                .colours
+                .iter()
+                .fold(Object::new(), |object, (colour, minimum)| {
+                    let mut inner = Object::new().number("count", minimum.count);
+                    inner = match minimum.draw {
+                        Some(draw) => inner.number("draw", draw),
+                        None => inner.raw("draw", "null"),
+                    };
+                    object.raw(colour, &inner.finish())
+                });
+            Object::new()
+                .number("game", bag.game)
+                .number("power", bag.power())
+                .raw("bag", &colours.finish())
+                .finish()
+        });
+        let stats = self
+            .stats
+            .iter()
+            .fold(Object::new(), |object, (colour, stats)| {
+                let histogram = stats.histogram.iter().fold(Object::new(), |h, (n, games)| {
+                    h.number(&n.to_string(), games)
+                });
+                let inner = Object::new()
+                    .number("min", stats.min)
+                    .number("mean", stats.mean)
+                    .number("max", stats.max)
+                    .number("largest_in", stats.largest_in)
+                    .raw("histogram", &histogram.finish());
+                object.raw(colour, &inner.finish())
+            });
+        let most_constraining = self
+            .most_constraining()
+            .map_or("null".to_string(), json::string);
+        Object::new()
+            .raw("games", &json::array(bags))
+            .raw("stats", &stats.finish())
+            .raw("most_constraining", &most_constraining)
+            .finish()
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: usize = 2;
+    type Input = Games;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_games(input, None)
+    }
+
+    fn part1(games: &Self::Input) -> usize {
+        feasible_games(&games.games, &puzzle_bag())
+            .feasible
+            .iter()
+            .sum()
+    }
+
+    fn part2(games: &Self::Input) -> usize {
+        games
+            .games
+            .iter()
+            .map(|game| game.minimum_bag(&games.colours).power())
+            .sum()
+    }
+}
+
+#[test]
+fn test_example() {
+    let games = Day2::parse(EXAMPLE).unwrap();
+    assert_eq!(Day2::part1(&games), 8);
+    assert_eq!(Day2::part2(&games), 2286);
+}
+
+#[test]
+fn test_colours() {
+    let input = "Game 1: 2 yellow, 1 red; 3 purple\nGame 2: 4 red, 1 yellow; 2 purple";
+    let games = Day2::parse(input).unwrap();
+    assert_eq!(games.colours.len(), 3);
+    assert_eq!(count(&games.games[0].sets[1], "purple"), 3);
+    assert_eq!(Day2::part2(&games), 6 + 8);
+    // No yellow or purple cubes in the part 1 bag
+    assert_eq!(Day2::part1(&games), 0);
+
+    let rgb: BTreeSet<_> = ["red", "green", "blue"].map(String::from).into();
+    let err = parse_games(input, Some(&rgb)).unwrap_err();
+    assert_eq!(
+        (err.line, err.column, err.found.as_str()),
+        (1, 11, "yellow")
+    );
+    let games = parse_games("Game 1: 1 red", Some(&rgb)).unwrap();
+    assert_eq!(Day2::part2(&games), 0);
+
+    let err = Day2::parse("Game 1: 1 red, 2 red").unwrap_err();
+    assert_eq!(err.column, 18);
+}
+
+#[test]
+fn test_feasibility() {
+    let games = Day2::parse(EXAMPLE).unwrap();
+    let result = feasible_games(&games.games, &puzzle_bag());
+    assert_eq!(result.feasible, [1, 2, 5]);
+    let (id, violation) = &result.infeasible[0];
+    assert_eq!(*id, 3);
+    assert_eq!(
+        violation.to_string(),
+        "draw 1 shows 20 red but the bag holds 12"
+    );
+    assert_eq!(result.infeasible[1].1.colour, "blue");
+
+    // Colours are checked in the order they were written
+    let game = &Day2::parse("Game 1: 13 red, 15 blue").unwrap().games[0];
+    let violation = game.check(&puzzle_bag()).unwrap_err();
+    assert_eq!(violation.colour, "red");
+
+    // What if the bag had more red?
+    let bag = parse_bag("20 red, 13 green, 14 blue").unwrap();
+    assert_eq!(feasible_games(&games.games, &bag).feasible, [1, 2, 3, 5]);
+    assert!(parse_bag("12 red, x green").is_err());
+}
+
+#[test]
+fn test_bag_report() {
+    let games = Day2::parse("Game 1: 3 blue, 4 red; 6 blue; 6 blue, 1 red\nGame 7: 2 red").unwrap();
+    let report = BagReport::new(&games);
+    let first = &report.bags[0];
+    assert_eq!(
+        first.colours["blue"],
+        Minimum {
+            count: 6,
+            draw: Some(2)
+        }
+    );
+    assert_eq!(first.colours["red"].draw, Some(1));
+    assert_eq!(report.bags[1].colours["blue"].draw, None);
+    assert_eq!(report.bags[1].power(), 0);
+
+    let blue = &report.stats["blue"];
+    assert_eq!((blue.min, blue.max, blue.mean), (0, 6, 3.0));
+    assert_eq!(blue.histogram, BTreeMap::from([(0, 1), (6, 1)]));
+    assert_eq!(report.stats["red"].largest_in, 1);
+    assert_eq!(report.most_constraining(), Some("blue"));
+
+    assert_eq!(
+        report.table(),
+        " Game      blue       red       Power
+    1     6 @2      4 @1           24
+    7     0 -       2 @1            0
+
+Colour      Min     Mean    Max  Largest in
+blue          0     3.00      6           1
+red           2     3.00      4           1
+Most constraining: blue
+"
+    );
+    assert_eq!(
+        report.to_json(),
+        concat!(
+            r#"{"games":[{"game":1,"power":24,"bag":{"blue":{"count":6,"draw":2},"red":{"count":4,"draw":1}}},"#,
+            r#"{"game":7,"power":0,"bag":{"blue":{"count":0,"draw":null},"red":{"count":2,"draw":1}}}],"#,
+            r#""stats":{"blue":{"min":0,"mean":3,"max":6,"largest_in":1,"histogram":{"0":1,"6":1}},"#,
+            r#""red":{"min":2,"mean":3,"max":4,"largest_in":1,"histogram":{"2":1,"4":1}}},"#,
+            r#""most_constraining":"blue"}"#
+        )
+    );
+}
+
+#[test]
+fn test_display() {
+    let games = Day2::parse(EXAMPLE).unwrap();
+    assert_eq!(
+        games.games[0].to_string(),
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+    );
+    assert_eq!(Day2::parse(&games.to_string()).unwrap(), games);
+}
+
+#[test]
+fn test_round_trip() {
+    let colours = ["red", "green", "blue", "yellow"];
+    for seed in 0..200 {
+        let mut rng = Rng::new(seed);
+        let games: Vec<Game> = (1..=1 + rng.below(10))
+            .map(|n| random_game(&mut rng, n, &colours))
+            .collect();
+        let text: String = games.iter().map(|g| format!("{g}\n")).collect();
+        let parsed = Day2::parse(&text).unwrap();
+        assert_eq!(parsed.games, games, "seed {seed}:\n{text}");
+        assert_eq!(parsed.to_string(), text);
+
+        let bag = puzzle_bag();
+        let fits = |set: &Draw| set.iter().all(|(c, n)| n <= count(&bag, c));
+        for game in &parsed.games {
+            // The violation is the first draw over the limit
+            match game.check(&bag) {
+                Ok(()) => assert!(game.sets.iter().all(fits)),
+                Err(v) => {
+                    let set = &game.sets[v.draw - 1];
+                    assert!(count(set, &v.colour) > count(&bag, &v.colour), "{game}");
+                    assert!(game.sets[..v.draw - 1].iter().all(fits));
+                }
+            }
+
+            let bag = game.minimum_bag(&parsed.colours);
+            let max = |colour: &str| game.sets.iter().map(|s| count(s, colour)).max().unwrap();
+            let power: usize = parsed.colours.iter().map(|c| max(c)).product();
+            assert_eq!(bag.power(), power, "{game}");
+            // The minimum bag is always enough
+            let minimum: Draw = bag
+                .colours
+                .iter()
+                .map(|(c, m)| (c.clone(), m.count))
+                .collect();
+            assert!(game.check(&minimum).is_ok(), "{game}");
+        }
+    }
 }
 
-pub fn solve() {
-    let games: Vec<Game> = INPUT.lines().map(|l| line_to_game(l)).collect();
-    let part1: usize = games
-        .iter()
-        .filter(|game| part_1_possible(game))
-        .map(|game| game.n)
-        .sum();
+#[cfg(test)]
+static EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
-    let part2: usize = games.iter().map(|game| power(game)).sum();
-    println!("Day 2");
-    println!("Part 1: {part1}");
-    println!("Part 2: {part2}");
+#[test]
+fn test_input() {
+    let games = Day2::parse(INPUT).unwrap();
+    assert_eq!(Day2::part1(&games), 2317);
+    assert_eq!(Day2::part2(&games), 74804);
 }
 
+#[cfg(test)]
 static INPUT: &str = "Game 1: 1 green, 6 red, 4 blue; 2 blue, 6 green, 7 red; 3 red, 4 blue, 6 green; 3 green; 3 blue, 2 green, 1 red
 Game 2: 2 blue, 4 red, 7 green; 17 red, 3 blue, 2 green; 3 green, 14 red, 1 blue
 Game 3: 12 blue, 3 red, 1 green; 8 blue, 9 red; 1 blue, 1 green, 9 red; 4 blue, 1 green, 9 red
@@ -1,202 +1,559 @@
-fn pad_input(input: &Vec<Vec<char>>) -> Vec<Vec<char>> {
-    let y_dim = input.len();
-    let x_dim = input[0].len();
-
-    let mut result = vec![vec!['.'; x_dim + 2]; y_dim + 2];
-    for y in 0..y_dim {
-        for x in 0..x_dim {
-            result[y + 1][x + 1] = input[y][x];
+use crate::error::parse_number;
+use crate::grid::Grid;
+use crate::{ParseError, Solution};
+
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
+}
+
+/// A number in the schematic, covering columns `start..end` of `row`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberSpan {
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+    pub value: usize,
+}
+
+/// Any character other than a digit or `.`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub x: usize,
+    pub y: usize,
+    pub c: char,
+}
+
+/// Every number and symbol in an engine schematic, and which of them touch, including
+/// diagonally. Numbers and symbols are in reading order and referred to by index.
+///
+/// Rows can have different lengths. A cell past the end of a row is empty.
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    pub numbers: Vec<NumberSpan>,
+    pub symbols: Vec<Symbol>,
+    grid: Grid<char>,
+    /// For each number, the symbols it touches.
+    number_symbols: Vec<Vec<usize>>,
+    /// For each symbol, the numbers it touches.
+    symbol_numbers: Vec<Vec<usize>>,
+}
+
+/// The number in columns `start..end` of row `y`, with any error placed there.
+fn parse_span(grid: &Grid<char>, y: usize, start: usize, end: usize) -> Result<usize, ParseError> {
+    let digits: String = grid.row(y)[start..end].iter().collect();
+    parse_number(&digits, &digits).map_err(|err| ParseError {
+        line: y + 1,
+        column: start + 1,
+        ..err
+    })
+}
+
+fn find_numbers(
+    grid: &Grid<char>,
+    y: usize,
+    numbers: &mut Vec<NumberSpan>,
+) -> Result<(), ParseError> {
+    let line = grid.row(y);
+    let mut x = 0;
+    while x < line.len() {
+        if !line[x].is_ascii_digit() {
+            x += 1;
+            continue;
         }
+        let end = line[x..]
+            .iter()
+            .position(|c| !c.is_ascii_digit())
+            .map_or(line.len(), |i| x + i);
+        numbers.push(NumberSpan {
+            row: y,
+            start: x,
+            end,
+            value: parse_span(grid, y, x, end)?,
+        });
+        x = end;
     }
-    result
+    Ok(())
 }
 
-fn get_number(padded_data: &Vec<Vec<char>>, y: usize, x: usize) -> usize {
-    let mut x1 = x;
-    let mut x2 = x;
-    while padded_data[y][x1 - 1].is_numeric() {
-        x1 = x1 - 1;
-    }
-    while padded_data[y][x2 + 1].is_numeric() {
-        x2 = x2 + 1;
+impl Schematic {
+    /// Reads one row per line. Lines may be any length, and short ones are padded with
+    /// empty cells.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::new(Grid::parse_padded(input, '.', "a character", Some)?)
     }
-    char_splice_to_usize(&padded_data[y][x1..=x2])
-}
 
-fn find_numbers_in_section(padded_data: &Vec<Vec<char>>, y: usize, x_left: usize) -> Vec<usize> {
-    let (a, b, c) = (
-        padded_data[y][x_left],
-        padded_data[y][x_left + 1],
-        padded_data[y][x_left + 2],
-    );
-    let mut result = Vec::new();
-    match (a.is_numeric(), b.is_numeric(), c.is_numeric()) {
-        (false, false, false) => {}
-        (true, false, false) => {
-            result.push(get_number(padded_data, y, x_left));
-        }
-        (false, true, false) => {
-            result.push(get_number(padded_data, y, x_left + 1));
-        }
-        (true, true, false) => {
-            result.push(get_number(padded_data, y, x_left));
+    /// Fails if a number is too big for a `usize`.
+    pub fn new(grid: Grid<char>) -> Result<Self, ParseError> {
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+        // Indices into `symbols` for each row, in column order
+        let mut row_symbols = Vec::new();
+        for (y, line) in grid.rows().enumerate() {
+            find_numbers(&grid, y, &mut numbers)?;
+            let mut in_row = Vec::new();
+            for (x, &c) in line.iter().enumerate().filter(|&(_, &c)| is_symbol(c)) {
+                in_row.push(symbols.len());
+                symbols.push(Symbol { x, y, c });
+            }
+            row_symbols.push(in_row);
         }
-        (false, false, true) => {
-            result.push(get_number(padded_data, y, x_left + 2));
+
+        // A number covering columns start..end touches a symbol in the row above, its own
+        // row or the row below when the symbol's column is in start - 1..=end
+        let mut number_symbols = Vec::with_capacity(numbers.len());
+        let mut symbol_numbers = vec![Vec::new(); symbols.len()];
+        for (i, number) in numbers.iter().enumerate() {
+            let mut touching = Vec::new();
+            for y in number.row.saturating_sub(1)..=number.row + 1 {
+                let Some(in_row) = row_symbols.get(y) else {
+                    continue;
+                };
+                let first = in_row.partition_point(|&s| symbols[s].x + 1 < number.start);
+                let overlapping = in_row[first..]
+                    .iter()
+                    .take_while(|&&s| symbols[s].x <= number.end);
+                touching.extend(overlapping.copied());
+            }
+            for &symbol in &touching {
+                symbol_numbers[symbol].push(i);
+            }
+            number_symbols.push(touching);
         }
-        (true, false, true) => {
-            result.push(get_number(padded_data, y, x_left));
-            result.push(get_number(padded_data, y, x_left + 2));
+        Ok(Schematic {
+            numbers,
+            symbols,
+            grid,
+            number_symbols,
+            symbol_numbers,
+        })
+    }
+
+    /// The schematic as it was read, with short rows padded.
+    pub fn grid(&self) -> &Grid<char> {
+        &self.grid
+    }
+
+    /// Indices of the symbols touching number `number`.
+    pub fn symbols_next_to(&self, number: usize) -> &[usize] {
+        &self.number_symbols[number]
+    }
+
+    /// Indices of the numbers touching symbol `symbol`.
+    pub fn numbers_next_to(&self, symbol: usize) -> &[usize] {
+        &self.symbol_numbers[symbol]
+    }
+
+    /// Numbers touching at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &NumberSpan> {
+        self.numbers
+            .iter()
+            .zip(&self.number_symbols)
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
+
+    /// Each symbol that is a gear under `rule`, with its value.
+    pub fn gears<'a>(&'a self, rule: &'a GearRule) -> impl Iterator<Item = (&'a Symbol, usize)> {
+        self.symbols
+            .iter()
+            .zip(&self.symbol_numbers)
+            .filter(|(symbol, numbers)| rule.is_gear(symbol.c, numbers.len()))
+            .map(|(symbol, numbers)| {
+                let values = numbers.iter().map(|&n| self.numbers[n].value);
+                (symbol, rule.aggregate.apply(values))
+            })
+    }
+}
+
+/// How many numbers a symbol must touch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Count {
+    Exactly(usize),
+    AtLeast(usize),
+    AtMost(usize),
+}
+
+impl Count {
+    fn matches(self, n: usize) -> bool {
+        match self {
+            Count::Exactly(count) => n == count,
+            Count::AtLeast(count) => n >= count,
+            Count::AtMost(count) => n <= count,
         }
-        (false, true, true) => {
-            result.push(get_number(padded_data, y, x_left + 1));
+    }
+}
+
+/// How a gear's numbers combine into its value. With no numbers a product is 1 and the
+/// others are 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Aggregate {
+    fn apply(self, values: impl Iterator<Item = usize>) -> usize {
+        match self {
+            Aggregate::Product => values.product(),
+            Aggregate::Sum => values.sum(),
+            Aggregate::Max => values.max().unwrap_or(0),
         }
-        (true, true, true) => {
-            result.push(get_number(padded_data, y, x_left));
+    }
+}
+
+/// Which symbols are gears and what each one is worth.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    /// Symbols that can be gears, or `None` for any symbol.
+    pub symbols: Option<Vec<char>>,
+    pub count: Count,
+    pub aggregate: Aggregate,
+}
+
+impl GearRule {
+    /// The puzzle's rule: a `*` next to exactly two numbers, worth their product.
+    pub fn puzzle() -> Self {
+        GearRule {
+            symbols: Some(vec!['*']),
+            count: Count::Exactly(2),
+            aggregate: Aggregate::Product,
         }
     }
-    result
+
+    fn is_gear(&self, symbol: char, numbers: usize) -> bool {
+        let symbol_ok = self.symbols.as_ref().is_none_or(|s| s.contains(&symbol));
+        symbol_ok && self.count.matches(numbers)
+    }
 }
 
-fn do_gear(padded_data: &Vec<Vec<char>>, x: usize, y: usize) -> Option<usize> {
-    let mut ratios: Vec<usize> = Vec::new();
-    let mut above = find_numbers_in_section(padded_data, y + 1, x - 1);
-    let mut below = find_numbers_in_section(padded_data, y - 1, x - 1);
-    ratios.append(&mut above);
-    ratios.append(&mut below);
+/// What a cell is drawn as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Empty,
+    PartNumber,
+    OtherNumber,
+    Gear(usize),
+    Symbol,
+}
 
-    let left = padded_data[y][x - 1];
-    if left.is_numeric() {
-        ratios.push(get_number(padded_data, y, x - 1));
+impl Mark {
+    fn ansi(self) -> &'static str {
+        match self {
+            Mark::Empty => "\x1b[2m",
+            Mark::PartNumber => "\x1b[1;32m",
+            Mark::OtherNumber => "\x1b[31m",
+            Mark::Gear(_) => "\x1b[1;30;43m",
+            Mark::Symbol => "\x1b[36m",
+        }
     }
 
-    let right = padded_data[y][x + 1];
-    if right.is_numeric() {
-        ratios.push(get_number(padded_data, y, x + 1));
+    fn class(self) -> &'static str {
+        match self {
+            Mark::Empty => "empty",
+            Mark::PartNumber => "part",
+            Mark::OtherNumber => "other",
+            Mark::Gear(_) => "gear",
+            Mark::Symbol => "symbol",
+        }
     }
-    if ratios.len() == 2 {
-        Some(ratios[0] * ratios[1])
-    } else {
-        None
+}
+
+const HTML_STYLE: &str = "body { background: #111; color: #ddd; }
+pre { font: 14px/1.2 monospace; }
+.empty { color: #555; }
+.part { color: #6d6; font-weight: bold; }
+.other { color: #e55; }
+.gear { background: #db3; color: #111; }
+.symbol { color: #5cd; }";
+
+fn escape_html(c: char) -> String {
+    match c {
+        '&' => "&amp;".to_string(),
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '"' => "&quot;".to_string(),
+        c => c.to_string(),
     }
 }
 
-fn part_2(padded_data: &Vec<Vec<char>>) -> usize {
-    let mut acc = 0;
-    for (y, line) in padded_data.iter().enumerate() {
-        for (x, c) in line.iter().enumerate() {
-            if c == &'*' {
-                match do_gear(padded_data, x, y) {
-                    Some(ratio) => acc = acc + ratio,
-                    None => {}
-                }
+impl Schematic {
+    /// Every cell with its mark, row by row.
+    fn marks(&self, rule: &GearRule) -> Vec<Vec<(char, Mark)>> {
+        let mut marks: Vec<Vec<_>> = self
+            .grid
+            .rows()
+            .map(|row| row.iter().map(|&c| (c, Mark::Empty)).collect())
+            .collect();
+        for (i, number) in self.numbers.iter().enumerate() {
+            let mark = if self.number_symbols[i].is_empty() {
+                Mark::OtherNumber
+            } else {
+                Mark::PartNumber
+            };
+            for cell in &mut marks[number.row][number.start..number.end] {
+                cell.1 = mark;
             }
         }
+        for symbol in &self.symbols {
+            marks[symbol.y][symbol.x].1 = Mark::Symbol;
+        }
+        for (symbol, ratio) in self.gears(rule) {
+            marks[symbol.y][symbol.x].1 = Mark::Gear(ratio);
+        }
+        marks
     }
-    acc
-}
 
-fn part_nearby(
-    data: &Vec<Vec<char>>,
-    y: usize,
-    x1: usize,
-    x2: usize,
-    y_max: usize,
-    x_max: usize,
-) -> bool {
-    let mut result = false;
-    // top
-    if y > 0 {
-        let start = if x1 > 0 { x1 - 1 } else { 0 };
-        let end = (x2 + 1).min(x_max - 1);
-        result = data[y - 1][start..=end]
-            .iter()
-            .any(|c| c != &'.' && !c.is_numeric());
-        if result {
-            return result;
+    /// The schematic coloured for a terminal: part numbers green, other numbers red, gears
+    /// highlighted and other symbols cyan. Each row ends with the ratios of its gears.
+    pub fn render_ansi(&self, rule: &GearRule) -> String {
+        let mut out = String::new();
+        for row in self.marks(rule) {
+            for run in row.chunk_by(|a, b| a.1 == b.1) {
+                out.push_str(run[0].1.ansi());
+                out.extend(run.iter().map(|&(c, _)| c));
+            }
+            out.push_str("\x1b[0m");
+            let ratios: Vec<String> = row
+                .iter()
+                .filter_map(|&(_, mark)| match mark {
+                    Mark::Gear(ratio) => Some(ratio.to_string()),
+                    _ => None,
+                })
+                .collect();
+            if !ratios.is_empty() {
+                out.push_str(&format!(
+                    "  {}{}\x1b[0m",
+                    Mark::Gear(0).ansi(),
+                    ratios.join(" ")
+                ));
+            }
+            out.push('\n');
         }
+        out
     }
-    // left
-    if x1 > 0 {
-        let c = &data[y][x1 - 1];
-        result = c != &'.' && !c.is_numeric();
-        if result {
-            return result;
+
+    /// A standalone HTML page of the schematic, marked like [`Schematic::render_ansi`].
+    /// Hovering over a gear shows its ratio.
+    pub fn render_html(&self, rule: &GearRule) -> String {
+        let mut body = String::new();
+        for row in self.marks(rule) {
+            for run in row.chunk_by(|a, b| a.1 == b.1) {
+                let text: String = run.iter().map(|&(c, _)| escape_html(c)).collect();
+                match run[0].1 {
+                    Mark::Gear(ratio) => body.push_str(&format!(
+                        "<span class=\"gear\" title=\"gear ratio {ratio}\">{text}</span>"
+                    )),
+                    mark => {
+                        body.push_str(&format!("<span class=\"{}\">{text}</span>", mark.class()))
+                    }
+                }
+            }
+            body.push('\n');
         }
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Engine schematic</title>\n<style>\n{HTML_STYLE}\n</style>\n</head>\n<body>\n<pre>\n{body}</pre>\n</body>\n</html>\n"
+        )
     }
-    // right
-    if x2 < x_max - 1 {
-        let c = &data[y][x2 + 1];
-        result = c != &'.' && !c.is_numeric();
-        if result {
-            return result;
-        }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: usize = 3;
+    type Input = Schematic;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Schematic::parse(input)
     }
 
-    // bottom
-    if y < y_max - 1 {
-        let start = if x1 > 0 { x1 - 1 } else { 0 };
-        let end = (x2 + 1).min(x_max - 1);
-        result = data[y + 1][start..=end]
-            .iter()
-            .any(|c| c != &'.' && !c.is_numeric());
+    fn part1(schematic: &Self::Input) -> usize {
+        schematic.part_numbers().map(|n| n.value).sum()
+    }
+
+    fn part2(schematic: &Self::Input) -> usize {
+        schematic
+            .gears(&GearRule::puzzle())
+            .map(|(_, ratio)| ratio)
+            .sum()
     }
-    result
 }
 
-fn char_splice_to_usize(cs: &[char]) -> usize {
-    cs.iter().fold(0, |acc, c| {
-        let x = c.to_digit(10).unwrap() as usize;
-        acc * 10 + x
-    })
+#[test]
+fn test_example() {
+    let data = Day3::parse(EXAMPLE).unwrap();
+    assert_eq!(Day3::part1(&data), 4361);
+    assert_eq!(Day3::part2(&data), 467835);
 }
 
-fn find_part_numbers(data: &Vec<Vec<char>>) -> Vec<usize> {
-    let y_max = data.len();
-    let mut numbers: Vec<usize> = Vec::default();
-    for (y, line) in data.iter().enumerate() {
-        let x_max = line.len();
-        let mut start_idx: Option<usize> = None;
-        for (x, c) in line.iter().enumerate() {
-            if x == x_max - 1 || !c.is_numeric() {
-                match start_idx {
-                    Some(idx) => {
-                        let x2 = if !c.is_numeric() { x - 1 } else { x };
-                        if part_nearby(&data, y, idx, x2, y_max, x_max) {
-                            let x = char_splice_to_usize(&line[idx..=x2]);
-                            numbers.push(x);
-                        }
-                        start_idx = None
-                    }
-                    None => {}
-                }
-            } else if c.is_numeric() {
-                match start_idx {
-                    Some(_) => {}
-                    None => {
-                        start_idx = Some(x);
-                    }
-                }
+#[test]
+fn test_schematic() {
+    // The same number three times, only one of them touching the symbol
+    let schematic = Day3::parse("12.12\n...*.\n12...").unwrap();
+    let spans: Vec<_> = schematic
+        .numbers
+        .iter()
+        .map(|n| (n.row, n.start, n.end, n.value))
+        .collect();
+    assert_eq!(spans, [(0, 0, 2, 12), (0, 3, 5, 12), (2, 0, 2, 12)]);
+    assert_eq!(schematic.symbols, [Symbol { x: 3, y: 1, c: '*' }]);
+    assert_eq!(schematic.numbers_next_to(0), [1]);
+    assert_eq!(schematic.symbols_next_to(0), []);
+    assert_eq!(schematic.part_numbers().count(), 1);
+    assert_eq!(schematic.gears(&GearRule::puzzle()).count(), 0);
+
+    let err = Day3::parse("..\n.12345678901234567890123*").err().unwrap();
+    assert_eq!((err.line, err.column), (2, 2));
+    assert_eq!(err.found, "12345678901234567890123");
+}
+
+#[test]
+fn test_gear_rules() {
+    let schematic = Day3::parse("1.2.3\n.#.*.\n4.5.6").unwrap();
+    let gears = |rule: &GearRule| -> Vec<(char, usize)> {
+        schematic.gears(rule).map(|(s, v)| (s.c, v)).collect()
+    };
+    assert_eq!(gears(&GearRule::puzzle()), []);
+
+    // Any symbol with three or more numbers around it
+    let crowded = GearRule {
+        symbols: None,
+        count: Count::AtLeast(3),
+        aggregate: Aggregate::Sum,
+    };
+    assert_eq!(gears(&crowded), [('#', 12), ('*', 16)]);
+
+    let busiest = GearRule {
+        symbols: Some(vec!['#']),
+        count: Count::AtMost(4),
+        aggregate: Aggregate::Max,
+    };
+    assert_eq!(gears(&busiest), [('#', 5)]);
+    let four = GearRule {
+        count: Count::Exactly(4),
+        ..GearRule::puzzle()
+    };
+    assert_eq!(gears(&four), [('*', 180)]);
+}
+
+/// Adjacency from checking the eight neighbours of every digit, which is how the schematic
+/// was first built. Only works on a rectangular grid.
+#[cfg(test)]
+fn grid_adjacency(schematic: &Schematic, grid: &Grid<char>) -> Vec<Vec<usize>> {
+    let mut symbol_at = Grid::new(grid.width(), grid.height(), None);
+    for (i, symbol) in schematic.symbols.iter().enumerate() {
+        symbol_at[(symbol.x, symbol.y)] = Some(i);
+    }
+    let touching = |number: &NumberSpan| {
+        let mut touching: Vec<usize> = (number.start..number.end)
+            .flat_map(|x| grid.neighbours8(x, number.row))
+            .filter_map(|p| symbol_at[p])
+            .collect();
+        touching.sort_unstable();
+        touching.dedup();
+        touching
+    };
+    schematic.numbers.iter().map(touching).collect()
+}
+
+#[test]
+fn test_span_overlap() {
+    use crate::rng::Rng;
+
+    let mut rng = Rng::new(2023);
+    for _ in 0..500 {
+        let rows: Vec<String> = (0..1 + rng.below(6))
+            .map(|_| {
+                (0..rng.below(9))
+                    .map(|_| match rng.below(10) {
+                        0..=3 => *rng.choose(&['1', '2', '3', '4', '5', '6', '7', '8', '9']),
+                        4..=7 => '.',
+                        _ => *rng.choose(&['*', '#', '+', '$']),
+                    })
+                    .collect()
+            })
+            .collect();
+        let input = rows.join("\n");
+        let ragged = Schematic::parse(&input).unwrap();
+
+        // Padding short rows with empty cells must not change anything
+        let width = rows.iter().map(String::len).max().unwrap().max(1);
+        let padded: Vec<String> = rows.iter().map(|r| format!("{r:.<width$}")).collect();
+        let grid = Grid::parse(&padded.join("\n"), "a character", Some).unwrap();
+        let rectangular = Schematic::new(grid.clone()).unwrap();
+        assert_eq!(ragged.numbers, rectangular.numbers, "{input}");
+        assert_eq!(ragged.symbols, rectangular.symbols, "{input}");
+
+        let expected = grid_adjacency(&rectangular, &grid);
+        for (i, symbols) in expected.iter().enumerate() {
+            assert_eq!(ragged.symbols_next_to(i), symbols, "{input}");
+            assert_eq!(rectangular.symbols_next_to(i), symbols, "{input}");
+            for &symbol in symbols {
+                assert!(ragged.numbers_next_to(symbol).contains(&i));
             }
         }
     }
-    numbers
 }
 
-pub fn solve() {
-    let data = INPUT
-        .lines()
-        .map(|l| l.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let part1: usize = find_part_numbers(&data).iter().sum();
-    let padded_data = pad_input(&data);
-    let part2 = part_2(&padded_data);
-    println!("Day 3");
-    println!("Part 1: {part1}");
-    println!("Part 2: {part2}");
+#[test]
+fn test_ragged_rows() {
+    // The 7 ends past the short row above it, and the # is past the end of the row below
+    let schematic = Day3::parse("1.\n..7\n.#..\n2").unwrap();
+    assert_eq!(schematic.grid().width(), 4);
+    assert_eq!(schematic.grid().row(3), ['2', '.', '.', '.']);
+    let parts: Vec<_> = schematic.part_numbers().map(|n| n.value).collect();
+    assert_eq!(parts, [7, 2]);
+}
+
+#[test]
+fn test_render() {
+    let schematic = Day3::parse("12*3\n..<.\n5...").unwrap();
+    let rule = GearRule::puzzle();
+    let marks: Vec<Vec<Mark>> = schematic
+        .marks(&rule)
+        .into_iter()
+        .map(|row| row.into_iter().map(|(_, mark)| mark).collect())
+        .collect();
+    let (e, p, o) = (Mark::Empty, Mark::PartNumber, Mark::OtherNumber);
+    assert_eq!(
+        marks,
+        [
+            vec![p, p, Mark::Gear(36), p],
+            vec![e, e, Mark::Symbol, e],
+            vec![o, e, e, e]
+        ]
+    );
+
+    let ansi = schematic.render_ansi(&rule);
+    assert_eq!(ansi.lines().count(), 3);
+    assert!(ansi.lines().next().unwrap().ends_with("36\x1b[0m"));
+    let html = schematic.render_html(&rule);
+    assert!(html.contains(r#"<span class="gear" title="gear ratio 36">*</span>"#));
+    assert!(html.contains(r#"<span class="symbol">&lt;</span>"#));
+}
+
+#[cfg(test)]
+static EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+#[test]
+fn test_input() {
+    let data = Day3::parse(INPUT).unwrap();
+    assert_eq!(Day3::part1(&data), 560670);
+    assert_eq!(Day3::part2(&data), 91622824);
 }
 
+#[cfg(test)]
 static INPUT: &str =
 "....573.613.........965............691......892..948.......964........439.375..................320......273...........352.284...............
 .......*.............*.....814...............$....*........../..94......*....=.............103............/..882*...........+...............
@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 
-struct Card {
+use crate::error::{parse_lines, parse_number};
+use crate::{ParseError, Solution};
+
+pub struct Card {
     winners: HashSet<usize>,
     selection: HashSet<usize>,
 }
@@ -10,7 +13,7 @@ fn find_num_winners(card: &Card) -> usize {
 }
 
 fn find_score(card: &Card) -> usize {
-    let n_winners = find_num_winners(&card);
+    let n_winners = find_num_winners(card);
     if n_winners == 0 {
         0
     } else {
@@ -18,7 +21,28 @@ fn find_score(card: &Card) -> usize {
     }
 }
 
-fn count(win: &Vec<usize>, n: usize) -> usize {
+/// How many copies of each card are processed, the original included, given how many
+/// numbers each card wins. A card's copies are final once every card before it has been
+/// seen, so one pass forwards is enough: `expire` holds the copies whose run of won cards
+/// ends at each index.
+pub fn copies(wins: &[usize]) -> Vec<usize> {
+    let mut copies = Vec::with_capacity(wins.len());
+    let mut expire = vec![0; wins.len() + 1];
+    let mut extra = 0;
+    for (n, &won) in wins.iter().enumerate() {
+        extra -= expire[n];
+        let card = 1 + extra;
+        copies.push(card);
+        extra += card;
+        expire[(n + 1 + won).min(wins.len())] += card;
+    }
+    copies
+}
+
+/// The original recursive count, which follows every copy and takes exponential time.
+/// Kept to check [`copies`] against.
+#[cfg(test)]
+fn count(win: &[usize], n: usize) -> usize {
     if n < win.len() {
         let num_wins = win[n];
         (n + 1..=n + num_wins).map(|x| count(win, x)).sum::<usize>() + 1
@@ -27,47 +51,108 @@ fn count(win: &Vec<usize>, n: usize) -> usize {
     }
 }
 
-fn count_wins(wins: &Vec<usize>) -> usize {
+#[cfg(test)]
+fn count_wins(wins: &[usize]) -> usize {
     let mut ct = 0;
 
     for (n, _) in wins.iter().enumerate() {
-        ct = ct + count(wins, n);
+        ct += count(wins, n);
     }
     ct
 }
 
-fn parse_line(line: &str) -> Card {
-    let mut split = line.split(':');
-    let mut winners_selection_split = split.nth(1).unwrap().split('|');
-    let winners_s = winners_selection_split.next().unwrap();
-    let selections_s = winners_selection_split.next().unwrap();
+fn parse_line(line: &str) -> Result<Card, ParseError> {
+    let (_, numbers) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::at_end(line, "`:`"))?;
+    let (winners_s, selections_s) = numbers
+        .split_once('|')
+        .ok_or_else(|| ParseError::at_end(line, "`|`"))?;
     let winners: HashSet<usize> = winners_s
         .split_ascii_whitespace()
-        .map(|win| win.parse().unwrap())
-        .collect();
+        .map(|win| parse_number(line, win))
+        .collect::<Result<_, _>>()?;
     let selection: HashSet<usize> = selections_s
         .split_ascii_whitespace()
-        .map(|win| win.parse().unwrap())
-        .collect();
-    Card {
-        winners: winners,
-        selection: selection,
+        .map(|win| parse_number(line, win))
+        .collect::<Result<_, _>>()?;
+    Ok(Card { winners, selection })
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: usize = 4;
+    type Input = Vec<Card>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, parse_line)
+    }
+
+    fn part1(cards: &Self::Input) -> usize {
+        cards.iter().map(find_score).sum()
+    }
+
+    fn part2(cards: &Self::Input) -> usize {
+        let wins: Vec<usize> = cards.iter().map(find_num_winners).collect();
+        copies(&wins).iter().sum()
     }
 }
 
-pub fn solve() {
-    let cards: Vec<Card> = INPUT.lines().map(|l| parse_line(l)).collect();
+#[test]
+fn test_example() {
+    let cards = Day4::parse(EXAMPLE).unwrap();
+    assert_eq!(Day4::part1(&cards), 13);
+    assert_eq!(Day4::part2(&cards), 30);
+}
+
+#[test]
+fn test_copies() {
+    use crate::rng::Rng;
+
+    assert_eq!(copies(&[4, 2, 2, 1, 0, 0]), [1, 2, 4, 8, 14, 1]);
+    assert_eq!(copies(&[]), []);
 
-    let part_1: usize = cards.iter().map(|card| find_score(&card)).sum();
+    // The same recursion as `count`, but tallying each card it reaches
+    fn visit(wins: &[usize], n: usize, copies: &mut [usize]) {
+        if n < wins.len() {
+            copies[n] += 1;
+            (n + 1..=n + wins[n]).for_each(|x| visit(wins, x, copies));
+        }
+    }
+    for seed in 0..300 {
+        let mut rng = Rng::new(seed);
+        let len = rng.below(12);
+        let wins: Vec<usize> = (0..len).map(|n| rng.below(len - n + 1)).collect();
+        let mut expected = vec![0; len];
+        (0..len).for_each(|n| visit(&wins, n, &mut expected));
+        assert_eq!(copies(&wins), expected, "{wins:?}");
+        assert_eq!(copies(&wins).iter().sum::<usize>(), count_wins(&wins));
+    }
+
+    // Far too deep for the recursion
+    let chain = copies(&vec![1; 100_000]);
+    assert_eq!(chain[99_999], 100_000);
+}
 
-    let wins: Vec<usize> = cards.iter().map(|game| find_num_winners(&game)).collect();
-    let part_2 = count_wins(&wins);
+#[cfg(test)]
+static EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
-    println!("Day 4");
-    println!("Part 1: {part_1}");
-    println!("Part 2: {part_2}");
+#[test]
+fn test_input() {
+    let cards = Day4::parse(INPUT).unwrap();
+    assert_eq!(Day4::part1(&cards), 21919);
+    assert_eq!(Day4::part2(&cards), 9881048);
 }
 
+#[cfg(test)]
 static INPUT: &str = "Card   1: 10  5 11 65 27 43 44 29 24 69 | 65 66 18 14 17 97 95 34 38 23 10 25 22 15 87  9 28 43  4 71 89 20 72  5  6
 Card   2: 25 43 15 31 45 19 36 73 34 85 | 92 11 85 68 74 20 19 71  1 36 43 32 77 33 14 31 73 15 45 83 34 25  6 88 57
 Card   3:  4 46 42 23 18 98 59 75 19 57 | 22  3 75 80 42 23 59 39 98 38 18 21 67 57 20 25 71 26 64  4 83 79 91 65 90
@@ -1,5 +1,8 @@
 use std::collections::VecDeque;
 
+use crate::error::parse_number;
+use crate::{ParseError, Solution};
+
 type Map = Vec<(usize, usize, usize)>;
 type Seeds = Vec<usize>;
 
@@ -47,9 +50,9 @@ fn merge_map(l_map: &Map, r_map: &Map) -> Map {
     let mut l_s = l_map.clone();
 
     // Sorting the first map by its outputs
-    l_s.sort_by(|a, b| a.1.cmp(&b.1));
+    l_s.sort_by_key(|a| a.1);
     // Sorting second map by its inputs
-    r_s.sort_by(|a, b| a.0.cmp(&b.0));
+    r_s.sort_by_key(|a| a.0);
 
     let mut l_q = VecDeque::from(l_s);
     let mut r_q = VecDeque::from(r_s);
@@ -119,83 +122,204 @@ fn find_output_map(input: usize, map: &Map) -> usize {
     input
 }
 
-fn find_output(seed: usize, maps: &Vec<Map>) -> usize {
-    maps.iter().fold(seed, |acc, map| find_output_map(acc, map))
+fn find_output(seed: usize, maps: &[Map]) -> usize {
+    maps.iter().fold(seed, find_output_map)
 }
 
-fn parse_map(map_str: &str) -> Map {
-    map_str
+fn parse_map(input: &str, map_str: &str) -> Result<Map, ParseError> {
+    let (_, ranges) = map_str
         .split_once('\n')
-        .unwrap()
-        .1
+        .ok_or_else(|| ParseError::at(input, &map_str[map_str.len()..], "a map range"))?;
+    ranges
         .lines()
         .map(|l| {
-            let mut nums = l.split_ascii_whitespace().map(|x| x.parse().unwrap());
-            let dest = nums.next().unwrap();
-            let src = nums.next().unwrap();
-            let range = nums.next().unwrap();
-            (src, dest, range)
+            let mut nums = l.split_ascii_whitespace().map(|x| parse_number(input, x));
+            let mut next = || {
+                nums.next()
+                    .unwrap_or_else(|| Err(ParseError::at(input, &l[l.len()..], "a number")))
+            };
+            let dest = next()?;
+            let src = next()?;
+            let range = next()?;
+            match l.split_ascii_whitespace().nth(3) {
+                Some(extra) => Err(ParseError::at(input, extra, "the end of the line")),
+                None => Ok((src, dest, range)),
+            }
         })
         .collect()
 }
 
-fn parse_maps(maps_str: &str) -> Vec<Map> {
-    maps_str.split("\n\n").map(parse_map).collect()
+fn parse_maps(input: &str, maps_str: &str) -> Result<Vec<Map>, ParseError> {
+    maps_str
+        .split("\n\n")
+        .map(|map_str| parse_map(input, map_str))
+        .collect()
 }
 
-fn parse_seeds(seeds_str: &str) -> Seeds {
-    seeds_str
-        .split_once(':')
-        .unwrap()
-        .1
+fn parse_seeds(input: &str, seeds_str: &str) -> Result<Seeds, ParseError> {
+    let seeds = seeds_str
+        .strip_prefix("seeds:")
+        .ok_or_else(|| ParseError::at(input, seeds_str, "`seeds:`"))?
         .split_ascii_whitespace()
-        .map(|x| x.parse().unwrap())
-        .collect()
+        .map(|x| parse_number(input, x))
+        .collect::<Result<Seeds, _>>()?;
+    if seeds.is_empty() {
+        let end = &seeds_str[seeds_str.len()..];
+        return Err(ParseError::at(input, end, "a seed"));
+    }
+    Ok(seeds)
 }
-fn parse_seeds_range(seeds_str: &str) -> Map {
-    seeds_str
-        .split_once(':')
-        .unwrap()
-        .1
+
+fn parse_seeds_range(input: &str, seeds_str: &str) -> Result<Map, ParseError> {
+    let seeds = parse_seeds(input, seeds_str)?;
+    if seeds.len() % 2 == 1 {
+        let end = &seeds_str[seeds_str.len()..];
+        return Err(ParseError::at(input, end, "a seed range length"));
+    }
+    // parse_seeds has checked the prefix, and every other number is a length
+    let lengths = seeds_str["seeds:".len()..]
         .split_ascii_whitespace()
-        .map(|x| x.parse().unwrap())
-        .array_chunks::<2>()
-        .map(|x| (x[0], x[0], x[1]))
-        .collect()
+        .skip(1)
+        .step_by(2);
+    if let Some((token, _)) = lengths
+        .zip(seeds.iter().skip(1).step_by(2))
+        .find(|&(_, &n)| n == 0)
+    {
+        return Err(ParseError::at(input, token, "a seed range length above 0"));
+    }
+    Ok(seeds.chunks_exact(2).map(|x| (x[0], x[0], x[1])).collect())
+}
+
+fn parse_input(input: &str) -> Result<Almanac, ParseError> {
+    let input = input.trim_end();
+    let (seeds_str, maps_str) = input.split_once("\n\n").ok_or_else(|| {
+        let first = input.lines().next().unwrap_or(input);
+        ParseError::at(input, &first[first.len()..], "a blank line")
+    })?;
+    Ok(Almanac {
+        seeds: parse_seeds(input, seeds_str)?,
+        seed_ranges: parse_seeds_range(input, seeds_str)?,
+        maps: parse_maps(input, maps_str)?,
+    })
+}
+
+pub struct Almanac {
+    seeds: Seeds,
+    seed_ranges: Map,
+    maps: Vec<Map>,
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: usize = 5;
+    type Input = Almanac;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(almanac: &Self::Input) -> usize {
+        almanac
+            .seeds
+            .iter()
+            .map(|s| find_output(*s, &almanac.maps))
+            .min()
+            .unwrap()
+    }
+
+    fn part2(almanac: &Self::Input) -> usize {
+        almanac
+            .maps
+            .iter()
+            .fold(almanac.seed_ranges.clone(), |acc, m| merge_map(&acc, m))
+            .iter()
+            .min_by(|a, b| a.1.cmp(&b.1))
+            .unwrap()
+            .1
+    }
 }
 
-fn parse_input_pt2(input: &str) -> (Map, Vec<Map>) {
-    let (seeds_str, maps_str) = input.split_once("\n\n").unwrap();
-    let seeds = parse_seeds_range(seeds_str);
-    let maps = parse_maps(maps_str);
-    (seeds, maps)
+#[test]
+fn test_parse_error() {
+    let input = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48 7";
+    let err = parse_input(input).err().unwrap();
+    assert_eq!((err.line, err.column), (5, 10));
+    assert_eq!(err.to_string(), "expected the end of the line, found `7`");
+
+    let err = parse_input("seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98")
+        .err()
+        .unwrap();
+    assert_eq!((err.line, err.column, err.found.as_str()), (4, 6, ""));
+
+    let err = parse_input("seeds:\n\nseed-to-soil map:\n50 98 2")
+        .err()
+        .unwrap();
+    assert_eq!((err.line, err.column), (1, 7));
+    assert_eq!(err.expected, "a seed");
+
+    let err = parse_input("seeds: 5 0 100 1\n\nseed-to-soil map:\n50 98 2")
+        .err()
+        .unwrap();
+    assert_eq!((err.line, err.column, err.found.as_str()), (1, 10, "0"));
 }
 
-fn parse_input(input: &str) -> (Seeds, Vec<Map>) {
-    let (seeds_str, maps_str) = input.split_once("\n\n").unwrap();
-    let seeds = parse_seeds(seeds_str);
-    let maps = parse_maps(maps_str);
-    (seeds, maps)
+#[test]
+fn test_example() {
+    let almanac = Day5::parse(EXAMPLE).unwrap();
+    assert_eq!(Day5::part1(&almanac), 35);
+    assert_eq!(Day5::part2(&almanac), 46);
 }
 
-pub fn solve() {
-    let (seeds, maps) = parse_input(INPUT);
-    let part_1: usize = seeds.iter().map(|s| find_output(*s, &maps)).min().unwrap();
-
-    let (seeds_range, _) = parse_input_pt2(INPUT);
-    let part_2 = maps
-        .iter()
-        .fold(seeds_range, |acc, m| merge_map(&acc, m))
-        .iter()
-        .min_by(|a, b| a.1.cmp(&b.1))
-        .unwrap()
-        .1;
-
-    println!("Day 5");
-    println!("Part 1: {part_1}");
-    println!("Part 2: {part_2}");
+#[cfg(test)]
+static EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+#[test]
+fn test_input() {
+    let almanac = Day5::parse(INPUT).unwrap();
+    assert_eq!(Day5::part1(&almanac), 318728750);
+    assert_eq!(Day5::part2(&almanac), 37384986);
 }
 
+#[cfg(test)]
 static INPUT: &str = "seeds: 202517468 131640971 1553776977 241828580 1435322022 100369067 2019100043 153706556 460203450 84630899 3766866638 114261107 1809826083 153144153 2797169753 177517156 2494032210 235157184 856311572 542740109
 
 seed-to-soil map:
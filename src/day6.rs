@@ -1,28 +1,120 @@
+use crate::error::parse_number;
+use crate::{ParseError, Solution};
+
 fn doit(t: usize, d: usize) -> (f64, f64) {
     let (tf, df) = (t as f64, d as f64);
-    return (
+    (
         -((tf.powf(2.0) - 4.0 * df).sqrt() - tf) / 2.0,
         ((tf.powf(2.0) - 4.0 * df).sqrt() + tf) / 2.0,
-    );
+    )
 }
 
 fn doit2(t: usize, d: usize) -> usize {
+    // Without two distinct roots no hold time beats the record, at best one ties it
+    let (tf, df) = (t as f64, d as f64);
+    if tf.powf(2.0) - 4.0 * df <= 0.0 {
+        return 0;
+    }
     let (a, b) = doit(t, d);
     let above = (a + 1.0).floor() as usize;
     let below = (b - 1.0).ceil() as usize;
-    let result = below - above + 1;
-    result as usize
+    below.checked_sub(above).map_or(0, |n| n + 1)
+}
+
+fn parse_line<'a>(input: &'a str, label: &str) -> Result<&'a str, ParseError> {
+    input
+        .lines()
+        .find_map(|l| l.strip_prefix(label))
+        .ok_or_else(|| ParseError::at(input, &input[input.len()..], format!("`{label}`")))
 }
 
-pub fn solve() {
-    let a = doit2(49, 263);
-    let b = doit2(97, 1532);
-    let c = doit2(94, 1378);
-    let d = doit2(94, 1851);
-    let pt1 = a * b * c * d;
+fn parse_numbers(input: &str, label: &str) -> Result<Vec<usize>, ParseError> {
+    parse_line(input, label)?
+        .split_ascii_whitespace()
+        .map(|x| parse_number(input, x))
+        .collect()
+}
 
-    let pt2 = doit2(49979494, 263153213781851);
-    println!("Day 6");
-    println!("Part 1: {pt1}");
-    println!("Part 2: {pt2}");
+fn parse_races(input: &str) -> Result<Vec<(usize, usize)>, ParseError> {
+    let times = parse_numbers(input, "Time:")?;
+    let distances = parse_numbers(input, "Distance:")?;
+    if times.len() != distances.len() {
+        let line = parse_line(input, "Distance:")?;
+        let expected = format!("{} distances", times.len());
+        return Err(ParseError::at(input, line.trim(), expected));
+    }
+    Ok(times.into_iter().zip(distances).collect())
 }
+
+// Part 2 reads each line as a single number with the spaces removed
+fn parse_race_kerning(input: &str) -> Result<(usize, usize), ParseError> {
+    let parse_kerned = |label| {
+        let line = parse_line(input, label)?;
+        line.split_ascii_whitespace()
+            .collect::<String>()
+            .parse()
+            .map_err(|_| ParseError::at(input, line.trim(), "a number"))
+    };
+    Ok((parse_kerned("Time:")?, parse_kerned("Distance:")?))
+}
+
+pub struct Races {
+    races: Vec<(usize, usize)>,
+    kerned: (usize, usize),
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: usize = 6;
+    type Input = Races;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Races {
+            races: parse_races(input)?,
+            kerned: parse_race_kerning(input)?,
+        })
+    }
+
+    fn part1(races: &Self::Input) -> usize {
+        races.races.iter().map(|&(t, d)| doit2(t, d)).product()
+    }
+
+    fn part2(races: &Self::Input) -> usize {
+        let (t, d) = races.kerned;
+        doit2(t, d)
+    }
+}
+
+#[test]
+fn test_example() {
+    let races = Day6::parse(EXAMPLE).unwrap();
+    assert_eq!(Day6::part1(&races), 288);
+    assert_eq!(Day6::part2(&races), 71503);
+    // The race with time 30 and record 200 ties the record exactly at 10 and 20
+    assert_eq!(doit2(30, 200), 9);
+    // Races that can only tie the record, or cannot even do that
+    assert_eq!(doit2(4, 4), 0);
+    assert_eq!(doit2(4, 5), 0);
+    assert_eq!(doit2(3, 2), 0);
+    assert_eq!(doit2(4, 3), 1);
+    assert_eq!(doit2(2, 0), 1);
+}
+
+#[cfg(test)]
+static EXAMPLE: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+#[test]
+fn test_input() {
+    let races = Day6::parse(INPUT).unwrap();
+    assert_eq!(races.kerned, (49979494, 263153213781851));
+    assert_eq!(Day6::part1(&races), 4403592);
+    assert_eq!(Day6::part2(&races), 38017587);
+}
+
+#[cfg(test)]
+static INPUT: &str = "Time:        49     97     94     94
+Distance:   263   1532   1378   1851";
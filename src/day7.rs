@@ -1,15 +1,18 @@
 use std::cmp::Ordering;
 
+use crate::error::{parse_lines, parse_number};
+use crate::{ParseError, Solution};
+
 #[derive(Debug, Clone)]
-struct Hand(Vec<usize>);
+pub struct Hand(Vec<usize>);
 
 fn hand_cmp_pt2(l: &Hand, r: &Hand) -> Ordering {
     let l_score = score_pt2(l);
     let r_score = score_pt2(r);
     if l_score > r_score {
-        return Ordering::Greater;
+        Ordering::Greater
     } else if l_score < r_score {
-        return Ordering::Less;
+        Ordering::Less
     } else {
         let mut l_iter = l.0.iter().peekable();
         let mut r_iter = r.0.iter().peekable();
@@ -30,9 +33,9 @@ fn hand_cmp(l: &Hand, r: &Hand) -> Ordering {
     let l_score = score(l);
     let r_score = score(r);
     if l_score > r_score {
-        return Ordering::Greater;
+        Ordering::Greater
     } else if l_score < r_score {
-        return Ordering::Less;
+        Ordering::Less
     } else {
         let mut l_iter = l.0.iter().peekable();
         let mut r_iter = r.0.iter().peekable();
@@ -53,13 +56,9 @@ fn hand_cmp(l: &Hand, r: &Hand) -> Ordering {
 fn score_pt2(hand: &Hand) -> usize {
     let mut map = [0; 15];
     let mut js = 0;
-    hand.0.iter().for_each(|c| {
-        if c == &1 {
-            js = js + 1
-        } else {
-            map[*c] = map[*c] + 1
-        }
-    });
+    hand.0
+        .iter()
+        .for_each(|c| if c == &1 { js += 1 } else { map[*c] += 1 });
     map.sort_by(|a, b| b.cmp(a));
 
     let first = map[0] + js;
@@ -93,7 +92,7 @@ fn score_pt2(hand: &Hand) -> usize {
 
 fn score(hand: &Hand) -> usize {
     let mut map = [0; 15];
-    hand.0.iter().for_each(|c| map[*c] = map[*c] + 1);
+    hand.0.iter().for_each(|c| map[*c] += 1);
     map.sort_by(|a, b| b.cmp(a));
     let first = map[0];
     let second = map[1];
@@ -124,87 +123,137 @@ fn score(hand: &Hand) -> usize {
     score
 }
 
-fn parse_pt2(input: &str) -> Vec<(Hand, usize)> {
-    input
-        .lines()
-        .map(|l| {
-            let mut split = l.split_ascii_whitespace();
-            let hand = split
-                .next()
-                .unwrap()
-                .chars()
-                .map(|c| match c {
-                    c if c.is_numeric() => c as usize - '0' as usize,
-                    'A' => 14,
-                    'K' => 13,
-                    'Q' => 12,
-                    'J' => 1,
-                    'T' => 10,
-                    _ => panic!("Invalid character {c}"),
-                })
-                .collect();
-            let bet = split.next().unwrap().parse().unwrap();
-            (Hand(hand), bet)
-        })
-        .collect()
+// Part 2 reads `J` as a joker, which is the weakest card on its own
+fn with_jokers(hand: &Hand) -> Hand {
+    Hand(
+        hand.0
+            .iter()
+            .map(|&c| if c == 11 { 1 } else { c })
+            .collect(),
+    )
 }
 
-fn parse(input: &str) -> Vec<(Hand, usize)> {
-    input
-        .lines()
-        .map(|l| {
-            let mut split = l.split_ascii_whitespace();
-            let hand = split
-                .next()
-                .unwrap()
-                .chars()
-                .map(|c| match c {
-                    c if c.is_numeric() => c as usize - '0' as usize,
-                    'A' => 14,
-                    'K' => 13,
-                    'Q' => 12,
-                    'J' => 11,
-                    'T' => 10,
-                    _ => panic!("Invalid character {c}"),
-                })
-                .collect();
-            let bet = split.next().unwrap().parse().unwrap();
-            (Hand(hand), bet)
+fn parse_line(line: &str) -> Result<(Hand, usize), ParseError> {
+    let mut split = line.split_ascii_whitespace();
+    let cards = split
+        .next()
+        .ok_or_else(|| ParseError::at(line, line, "a hand"))?;
+    let hand = cards
+        .char_indices()
+        .map(|(i, c)| match c {
+            '2'..='9' => Ok(c as usize - '0' as usize),
+            'A' => Ok(14),
+            'K' => Ok(13),
+            'Q' => Ok(12),
+            'J' => Ok(11),
+            'T' => Ok(10),
+            _ => Err(ParseError::at(
+                line,
+                &cards[i..i + c.len_utf8()],
+                "a card (one of `AKQJT98765432`)",
+            )),
         })
-        .collect()
+        .collect::<Result<Vec<_>, _>>()?;
+    if hand.len() != 5 {
+        return Err(ParseError::at(line, cards, "a hand of 5 cards"));
+    }
+    let bet = match split.next() {
+        Some(bet) => parse_number(line, bet)?,
+        None => return Err(ParseError::at_end(line, "a bid")),
+    };
+    Ok((Hand(hand), bet))
 }
 
-pub fn solve() {
-    let hands = parse(INPUT);
-    let mut hands_sorted_pt1 = hands.clone();
-    hands_sorted_pt1.sort_by(|a, b| hand_cmp(&a.0, &b.0));
-    let part_1_sol: usize = hands_sorted_pt1
-        .iter()
-        .enumerate()
-        .map(|x| {
-            let rank = x.0 + 1;
-            (rank, x.1 .0.clone(), x.1 .1, rank * x.1 .1)
-        })
-        .map(|x| x.3)
-        .sum();
+fn parse(input: &str) -> Result<Vec<(Hand, usize)>, ParseError> {
+    parse_lines(input, parse_line)
+}
 
-    let mut hands_pt2 = parse_pt2(INPUT);
-    hands_pt2.sort_by(|a, b| hand_cmp_pt2(&a.0, &b.0));
-    let part_2_sol: usize = hands_pt2
+// Total winnings of hands already sorted from weakest to strongest
+fn winnings(sorted_hands: &[(Hand, usize)]) -> usize {
+    sorted_hands
         .iter()
         .enumerate()
-        .map(|x| {
-            let rank = x.0 + 1;
-            (rank, x.1 .0.clone(), x.1 .1, rank * x.1 .1)
-        })
-        .map(|x| x.3)
-        .sum();
+        .map(|(i, (_, bet))| (i + 1) * bet)
+        .sum()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: usize = 7;
+    type Input = Vec<(Hand, usize)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(hands: &Self::Input) -> usize {
+        let mut hands = hands.clone();
+        hands.sort_by(|a, b| hand_cmp(&a.0, &b.0));
+        winnings(&hands)
+    }
+
+    fn part2(hands: &Self::Input) -> usize {
+        let mut hands_pt2: Vec<_> = hands
+            .iter()
+            .map(|(hand, bet)| (with_jokers(hand), *bet))
+            .collect();
+        hands_pt2.sort_by(|a, b| hand_cmp_pt2(&a.0, &b.0));
+        winnings(&hands_pt2)
+    }
+}
+
+#[test]
+fn test_example() {
+    let hands = Day7::parse(EXAMPLE).unwrap();
+    assert_eq!(Day7::part1(&hands), 6440);
+    assert_eq!(Day7::part2(&hands), 5905);
+}
+
+#[test]
+fn test_jokers() {
+    let hand = |s: &str| with_jokers(&parse_line(&format!("{s} 1")).unwrap().0);
+
+    assert_eq!(score_pt2(&hand("JJJJJ")), 6);
+    assert_eq!(score_pt2(&hand("JJJJ2")), 6);
+    assert_eq!(score_pt2(&hand("QJJQ2")), 5);
+    assert_eq!(score_pt2(&hand("KTJJT")), 5);
+    assert_eq!(score_pt2(&hand("T55J5")), 5);
+    assert_eq!(score_pt2(&hand("2233J")), 4);
+    assert_eq!(score_pt2(&hand("JJ234")), 3);
+    assert_eq!(score_pt2(&hand("J2345")), 1);
+    assert_eq!(score_pt2(&hand("23456")), 0);
+
+    // Without jokers the same hands score as dealt
+    assert_eq!(score(&parse_line("KTJJT 1").unwrap().0), 2);
+    assert_eq!(score(&parse_line("JJJJJ 1").unwrap().0), 6);
+
+    // A joker is the weakest card when breaking ties
+    assert_eq!(hand_cmp_pt2(&hand("JKKK2"), &hand("QQQQ2")), Ordering::Less);
+    assert_eq!(hand_cmp_pt2(&hand("J2222"), &hand("22222")), Ordering::Less);
+    assert_eq!(
+        hand_cmp_pt2(&hand("JJJJJ"), &hand("JJJJJ")),
+        Ordering::Equal
+    );
+}
+
+#[cfg(test)]
+static EXAMPLE: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
 
-    println!("Day 7");
-    println!("Part 1: {part_1_sol}");
-    println!("Part 2: {part_2_sol}");
+#[test]
+fn test_input() {
+    let hands = Day7::parse(INPUT).unwrap();
+    assert_eq!(Day7::part1(&hands), 249638405);
+    assert_eq!(Day7::part2(&hands), 249776650);
 }
 
+#[cfg(test)]
 static INPUT: &str = "JJJJJ 435
 29QA4 847
 6A9A9 348
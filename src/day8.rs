@@ -1,58 +1,84 @@
-use std::collections::{HashMap, HashSet};
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::{Arc, Barrier};
-use std::thread;
-use std::time::Instant;
+use std::collections::HashMap;
+
+use crate::{ParseError, Solution, SolveError};
 
 type Graph = Vec<[usize; 2]>;
 
-fn parse_map(input: &str) -> (Graph, HashMap<&str, usize>) {
-    let mut split = input.split("\n\n");
-    let graph_s = split.nth(1).unwrap();
-    let nodes_s: Vec<_> = graph_s
+fn split_sections(input: &str) -> Result<(&str, &str), ParseError> {
+    input.split_once("\n\n").ok_or_else(|| {
+        let first = input.lines().next().unwrap_or(input);
+        ParseError::at(input, &first[first.len()..], "a blank line")
+    })
+}
+
+fn parse_node<'a>(input: &str, line: &'a str) -> Result<(&'a str, &'a str, &'a str), ParseError> {
+    let (node, targets) = line
+        .split_once(" = ")
+        .ok_or_else(|| ParseError::at(input, &line[line.len()..], "` = `"))?;
+    let (l, r) = targets
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .and_then(|t| t.split_once(", "))
+        .ok_or_else(|| ParseError::at(input, targets, "`(<left>, <right>)`"))?;
+    Ok((node, l, r))
+}
+
+fn parse_map(input: &str) -> Result<(Graph, HashMap<String, usize>), ParseError> {
+    let (_, graph_s) = split_sections(input)?;
+    let nodes_s = graph_s
         .lines()
-        .map(|s| {
-            let node = &s[0..=2];
-            let l = &s[7..=9];
-            let r = &s[12..=14];
-            (node, l, r)
-        })
-        .collect();
-    let node_to_id = nodes_s
-        .iter()
-        .enumerate()
-        .map(|n| (n.1 .0, n.0))
-        .collect::<HashMap<&str, usize>>();
+        .map(|l| parse_node(input, l))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut node_to_id = HashMap::with_capacity(nodes_s.len());
+    for (i, (node, _, _)) in nodes_s.iter().enumerate() {
+        if node_to_id.insert(node.to_string(), i).is_some() {
+            return Err(ParseError::at(input, node, "a node not already defined"));
+        }
+    }
+    let find = |n: &str| {
+        node_to_id
+            .get(n)
+            .copied()
+            .ok_or_else(|| ParseError::at(input, n, "a known node"))
+    };
     let mut result: Vec<[usize; 2]> = vec![[0, 0]; node_to_id.len()];
-    nodes_s.iter().for_each(|(n, l, r)| {
-        let n_id = node_to_id.get(n).unwrap();
-        let l_id = node_to_id.get(l).unwrap();
-        let r_id = node_to_id.get(r).unwrap();
-        result[*n_id][0] = *l_id;
-        result[*n_id][1] = *r_id;
-    });
+    for (n, l, r) in &nodes_s {
+        let n_id = node_to_id[*n];
+        result[n_id][0] = find(l)?;
+        result[n_id][1] = find(r)?;
+    }
 
-    (result, node_to_id)
+    Ok((result, node_to_id))
 }
 
-fn parse_path(input: &str) -> Vec<usize> {
-    let mut split = input.split("\n\n");
-    let path_s = split.next().unwrap();
+fn parse_path(input: &str) -> Result<Vec<usize>, ParseError> {
+    let (path_s, _) = split_sections(input)?;
+    if path_s.is_empty() {
+        return Err(ParseError::at(input, path_s, "`L` or `R`"));
+    }
     path_s
-        .chars()
-        .map(|c| match c {
-            'L' => 0,
-            'R' => 1,
-            _ => panic!("Invalid char {c}"),
+        .char_indices()
+        .map(|(i, c)| match c {
+            'L' => Ok(0),
+            'R' => Ok(1),
+            _ => Err(ParseError::at(
+                input,
+                &path_s[i..i + c.len_utf8()],
+                "`L` or `R`",
+            )),
         })
         .collect()
 }
 
-fn count_nodes_til(graph: &Graph, path: &Vec<usize>, start_id: usize, end_id: usize) -> usize {
+// After one step per node and direction every state has come up, so the end is unreachable
+fn count_nodes_til(graph: &Graph, path: &[usize], start_id: usize, end_id: usize) -> Option<usize> {
     let mut n = start_id;
     let mut ct = 0;
     let mut path_idx = 0;
     while n != end_id {
+        if ct == graph.len() * path.len() {
+            return None;
+        }
         ct += 1;
         let dir = path[path_idx];
         n = graph[n][dir];
@@ -61,279 +87,151 @@ fn count_nodes_til(graph: &Graph, path: &Vec<usize>, start_id: usize, end_id: us
             path_idx = 0;
         }
     }
-    ct
-}
-
-pub fn step(graph: &Graph, id: usize, dir: usize) -> usize {
-    graph[id][dir]
+    Some(ct)
 }
 
-//fn solve_pt2_mulithread(
-//    graph: &Graph,
-//    path: &Vec<usize>,
-//    node_to_id: &HashMap<&str, usize>,
-//) -> usize {
-//    let z_nodes: HashSet<usize> = node_to_id
-//        .iter()
-//        .filter_map(|(s, i)| match s.chars().last().unwrap() {
-//            'Z' => Some(*i),
-//            _ => None,
-//        })
-//        .collect();
-//
-//    let n_threads = 10;
-//    let mut handles = Vec::with_capacity(n_threads);
-//
-//    let barrier = Arc::new(Barrier::new(n_threads));
-//    let
-//    for _ in 0..n_threads {
-//        let c = Arc::clone(&barrier);
-//    }
-//
-//    todo!()
-//}
-
-pub fn find_loop(graph: &Graph, path: &Vec<usize>, start_id: usize) -> (Vec<usize>, usize) {
-    let mut visited = HashMap::<(usize, usize), usize>::default();
-    let mut cur_id = start_id;
-    let mut history = Vec::<usize>::default();
-    let mut path_idx = 0;
-    while !visited.contains_key(&(cur_id, path_idx)) {
-        visited.insert((cur_id, path_idx), history.len());
-        history.push(cur_id);
-        let dir = path[path_idx];
-        cur_id = graph[cur_id][dir];
-        path_idx = if path_idx + 1 >= path.len() {
-            0
-        } else {
-            path_idx + 1
-        }
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
     }
-    let loop_start = visited.get(&(cur_id, path_idx)).unwrap();
-    let loop_length = visited.len() - loop_start;
-    println!("{loop_start}, {loop_length}");
-    (history, *loop_start)
 }
 
-type Loops = Vec<(Vec<usize>, usize)>;
-
-struct SharedData {
-    buffer_barrier: Barrier,
-    check_barrier: Barrier,
-    loops: Loops,
-    found_step: AtomicUsize,
-    counter: AtomicUsize,
-    buffer: Vec<usize>,
-}
-
-impl SharedData {
-    fn new(n_threads: usize, buffer_size: usize, loops: &Loops) -> Self {
-        SharedData {
-            buffer_barrier: Barrier::new(n_threads),
-            check_barrier: Barrier::new(n_threads),
-            loops: loops.clone(),
-            found_step: AtomicUsize::new(0),
-            counter: AtomicUsize::new(0),
-            buffer: vec![0; buffer_size],
+// Bounded like `count_nodes_til`, plus one step since the start itself does not count
+fn count_nodes_til_z(
+    graph: &Graph,
+    path: &[usize],
+    start_id: usize,
+    z_nodes: &[bool],
+) -> Option<usize> {
+    let mut n = start_id;
+    let mut ct = 0;
+    while !z_nodes[n] || ct == 0 {
+        if ct > graph.len() * path.len() {
+            return None;
         }
+        n = graph[n][path[ct % path.len()]];
+        ct += 1;
     }
+    Some(ct)
 }
 
-fn process_buffer(
-    n_threads: usize,
-    thread_id: usize,
-    path_idx: &mut usize,
-    shared_data: &SharedData,
-) {
-    let process_len = shared_data.buffer.len() / n_threads;
-    let start_idx = process_len * thread_id;
+// Puzzle inputs are built so each ghost reaches its first Z node after exactly one trip
+// around its loop, and so all of them line up on the least common multiple of those
+// counts. Nothing checks this, and other inputs can get a wrong answer.
+pub fn solve_pt2_lcm(
+    graph: &Graph,
+    path: &[usize],
+    node_to_id: &HashMap<String, usize>,
+) -> Result<usize, SolveError> {
+    let mut z_nodes = vec![false; node_to_id.len()];
+    node_to_id
+        .iter()
+        .filter(|(s, _)| s.ends_with('Z'))
+        .for_each(|(_, i)| z_nodes[*i] = true);
 
-    let mut data_ptr = unsafe { (shared_data.buffer.as_ptr() as *mut usize).add(start_idx) };
-    let the_loop = &shared_data.loops[thread_id].0;
-    let loop_start = shared_data.loops[thread_id].1;
-    for _ in 0..process_len {
-        unsafe { *data_ptr = the_loop[*path_idx] }
-        data_ptr = unsafe { data_ptr.add(1) };
-        *path_idx += 1;
-        if *path_idx >= the_loop.len() {
-            *path_idx = loop_start;
-        }
+    let mut starts = node_to_id
+        .iter()
+        .filter(|(s, _)| s.ends_with('A'))
+        .peekable();
+    if starts.peek().is_none() {
+        return Err(SolveError::new("no node ends in A"));
     }
+    starts.try_fold(1, |acc, (name, i)| {
+        let ct = count_nodes_til_z(graph, path, *i, &z_nodes).ok_or_else(|| {
+            SolveError::new(format!("no node ending in Z is reachable from {name}"))
+        })?;
+        Ok(acc / gcd(acc, ct) * ct)
+    })
 }
 
-fn check_matching_node(n_threads: usize, thread_id: usize, shared_data: &SharedData) {}
-
-fn run_thread(n_threads: usize, thread_id: usize, shared_data: &SharedData) {
-    // Using found_step like this does not allow the possiblity for the found node to be
-    // on the first node. If this were real, need to add a pre-check if the first node is
-    // match
-
-    loop {
-        let mut step_count = 0;
-        let mut path_idx = 0;
-        let found = unsafe { *shared_data.found_step.as_ptr() } != 0;
-        if !found {
-            // Process the buffer
-            process_buffer(n_threads, thread_id, &mut path_idx, shared_data);
-            shared_data.buffer_barrier.wait();
-            // Check for matching node in the buffer
-            shared_data.check_barrier.wait();
-        }
-    }
+pub struct Network {
+    path: Vec<usize>,
+    graph: Graph,
+    node_to_id: HashMap<String, usize>,
 }
 
-fn find_count_multithreaded(loops: &Vec<(Vec<usize>, usize)>) -> usize {
-    let n_threads = loops.len(); // or number of cores
-    let buffer_size = 100000 * loops.len();
+pub struct Day8;
 
-    let shared_data = Arc::new(SharedData::new(n_threads, buffer_size, loops));
+impl Solution for Day8 {
+    const DAY: usize = 8;
+    type Input = Network;
+    type Part1 = Result<usize, SolveError>;
+    type Part2 = Result<usize, SolveError>;
 
-    let mut handles = Vec::with_capacity(n_threads);
-    for thread_id in 0..n_threads {
-        let data = shared_data.clone();
-        handles.push(thread::spawn(move || {
-            run_thread(n_threads, thread_id, &data);
-        }));
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (graph, node_to_id) = parse_map(input)?;
+        Ok(Network {
+            path: parse_path(input)?,
+            graph,
+            node_to_id,
+        })
     }
 
-    for h in handles {
-        h.join().unwrap();
+    fn part1(network: &Self::Input) -> Result<usize, SolveError> {
+        let id = |node| {
+            network
+                .node_to_id
+                .get(node)
+                .copied()
+                .ok_or_else(|| SolveError::new(format!("no node {node}")))
+        };
+        count_nodes_til(&network.graph, &network.path, id("AAA")?, id("ZZZ")?)
+            .ok_or_else(|| SolveError::new("ZZZ cannot be reached from AAA"))
     }
 
-    todo!()
-}
-
-fn find_count(loops: &Vec<(Vec<usize>, usize)>) -> usize {
-    let mut found = false;
-    let mut steps = vec![0; loops.len()];
-    let mut ct = 0;
-    let now = Instant::now();
-    while !found {
-        found = loops.iter().enumerate().all(|(i, l)| {
-            let is_node = l.0[steps[i]] == 1;
-            steps[i] = if steps[i] + 1 >= l.0.len() {
-                l.1
-            } else {
-                steps[i] + 1
-            };
-            is_node
-        });
-        ct += 1;
-
-        if (ct % 10000000 == 0) {
-            let elapsed = now.elapsed().as_millis();
-            println!("{}/s", ct as u128 / elapsed)
-        }
+    fn part2(network: &Self::Input) -> Result<usize, SolveError> {
+        solve_pt2_lcm(&network.graph, &network.path, &network.node_to_id)
     }
-    ct
 }
 
-pub fn solve_pt2_faster(
-    graph: &Graph,
-    path: &Vec<usize>,
-    node_to_id: &HashMap<&str, usize>,
-) -> usize {
-    let z_nodes: Vec<usize> = {
-        let z_nodes: HashSet<usize> = node_to_id
-            .iter()
-            .filter_map(|(s, i)| match s.chars().last().unwrap() {
-                'Z' => Some(*i),
-                _ => None,
-            })
-            .collect();
-        let mut vec = vec![0; node_to_id.len()];
-        z_nodes.iter().for_each(|x| vec[*x] = 1);
-        vec
-    };
-
-    let a_nodes: HashSet<usize> = node_to_id
-        .iter()
-        .filter_map(|(s, i)| match s.chars().last().unwrap() {
-            'A' => Some(*i),
-            _ => None,
-        })
-        .collect();
-
-    let loops: Vec<_> = a_nodes
-        .iter()
-        .map(|x| {
-            let (mut nodes, loop_start) = find_loop(graph, path, *x);
-            nodes
-                .iter_mut()
-                .for_each(|x| *x = if z_nodes.contains(x) { 1 } else { 0 });
-            (nodes, loop_start)
-        })
-        .collect();
-    find_count(&loops);
-
-    todo!()
+#[test]
+fn test_example() {
+    assert_eq!(Day8::part1(&Day8::parse(EXAMPLE).unwrap()), Ok(6));
+    assert_eq!(Day8::part1(&Day8::parse(EXAMPLE3).unwrap()), Ok(2));
+    let network = Day8::parse(EXAMPLE2).unwrap();
+    assert_eq!(Day8::part1(&network), Err(SolveError::new("no node AAA")));
+    assert_eq!(Day8::part2(&network), Ok(6));
 }
 
-// ~7200/s
-pub fn solve_pt2(graph: &Graph, path: &Vec<usize>, node_to_id: &HashMap<&str, usize>) -> usize {
-    let mut nodes: Vec<usize> = node_to_id
-        .iter()
-        .filter_map(|(s, i)| match s.chars().last().unwrap() {
-            'A' => Some(*i),
-            _ => None,
-        })
-        .collect();
-    let z_nodes: Vec<usize> = {
-        let z_nodes: HashSet<usize> = node_to_id
-            .iter()
-            .filter_map(|(s, i)| match s.chars().last().unwrap() {
-                'Z' => Some(*i),
-                _ => None,
-            })
-            .collect();
-        let mut vec = vec![0; node_to_id.len()];
-        z_nodes.iter().for_each(|x| vec[*x] = 1);
-        vec
-    };
-
-    let now = Instant::now();
-
-    let mut found = false;
-    let mut ct = 0;
-    let mut path_idx = 0;
-    while !found {
-        ct += 1;
-        let dir = path[path_idx];
-        nodes.iter_mut().for_each(|x| *x = graph[*x][dir]);
-        found = nodes.iter().all(|x| z_nodes[*x] == 1);
-
-        path_idx += 1;
-        if path_idx >= path.len() {
-            path_idx = 0;
-        }
-
-        if (ct % 10000000 == 0) {
-            let elapsed = now.elapsed().as_millis();
-            println!("{}/s", ct as u128 / elapsed)
-        }
-    }
-    ct
-}
-
-pub fn solve() {
-    let path = parse_path(INPUT);
-    let (graph, node_to_id) = parse_map(INPUT);
-    //let zzz_id = node_to_id.get(&"ZZZ").unwrap();
-    //let aaa_id = node_to_id.get(&"AAA").unwrap();
-    //let pt1 = count_nodes_til(&graph, &path, *aaa_id, *zzz_id);
-    let pt2 = solve_pt2_faster(&graph, &path, &node_to_id);
-    //let pt2 = solve_pt2(&graph, &path, &node_to_id);
-    println!("Day 8");
-    //println!("Part 1: {pt1}");
-    println!("Part 2: {pt2}");
+#[test]
+fn test_bad_network() {
+    let err = Day8::parse("\n\nAAA = (AAA, AAA)").err().unwrap();
+    assert_eq!(
+        (err.line, err.column, err.expected.as_str()),
+        (1, 1, "`L` or `R`")
+    );
+    let err = Day8::parse("L\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)")
+        .err()
+        .unwrap();
+    assert_eq!(
+        (err.line, err.column, err.expected.as_str()),
+        (4, 1, "a node not already defined")
+    );
+
+    let network = Day8::parse("L\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+    let err = Day8::part1(&network).unwrap_err();
+    assert_eq!(err.to_string(), "ZZZ cannot be reached from AAA");
+    let err = Day8::part2(&network).unwrap_err();
+    assert_eq!(err.to_string(), "no node ending in Z is reachable from AAA");
+    let network = Day8::parse("L\n\nAAA = (AAA, AAA)").unwrap();
+    assert_eq!(Day8::part1(&network), Err(SolveError::new("no node ZZZ")));
+    let network = Day8::parse("L\n\nZZZ = (ZZZ, ZZZ)").unwrap();
+    assert_eq!(
+        Day8::part2(&network),
+        Err(SolveError::new("no node ends in A"))
+    );
 }
 
+#[cfg(test)]
 static EXAMPLE: &str = "LLR
 
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
 
+#[cfg(test)]
 static EXAMPLE2: &str = "LR
 
 11A = (11B, XXX)
@@ -345,6 +243,25 @@ static EXAMPLE2: &str = "LR
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
 
+#[cfg(test)]
+static EXAMPLE3: &str = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+#[test]
+fn test_input() {
+    let network = Day8::parse(INPUT).unwrap();
+    assert_eq!(Day8::part1(&network), Ok(19783));
+    assert_eq!(Day8::part2(&network), Ok(9177460370549));
+}
+
+#[cfg(test)]
 static INPUT: &str = "LRRLRLRRLRRRLRLRLRRLRRRLRRRLRRLRRRLRLRLRLRLRLRLRRRLRRLRRRLLLLRRRLRLLLRRRLLRLLRRRLRRRLRLRRLRRRLRRRLLRRRLRLRRRLLRRRLRLLRRRLRRLLRLRLRLRRRLRLLRLRLRRRLRLLRLRLRRRLLRRRLRRLRRRLRLRRLRLRRLRLRRLRRRLLRRRLLLRRRLLRRLRRLRRLRLLRRLRRRLRRLRLRLRRLRRLLLRRLRLRRRLRRRLRRRLLLRLRRRLLRRRLRLLRRRR
 
 NFK = (LMH, RSS)
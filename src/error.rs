@@ -0,0 +1,153 @@
+use std::error::Error;
+use std::fmt;
+
+/// Where and why a puzzle input failed to parse.
+///
+/// `line` and `column` are 1-based, `column` counting characters rather than bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: Option<usize>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    /// The offending text, empty when the input ended early.
+    pub found: String,
+}
+
+impl ParseError {
+    /// An error at `at`, which must be a subslice of `text`. Parsers that split their
+    /// input into pieces can pass the whole input as `text`, since every piece is a slice
+    /// of it.
+    pub fn at(text: &str, at: &str, expected: impl Into<String>) -> Self {
+        let (line, column) = position(text, at);
+        ParseError {
+            day: None,
+            line,
+            column,
+            expected: expected.into(),
+            found: at.to_string(),
+        }
+    }
+
+    /// An error at the end of `text`, for when a token is missing.
+    pub fn at_end(text: &str, expected: impl Into<String>) -> Self {
+        Self::at(text, &text[text.len()..], expected)
+    }
+
+    /// Moves an error built against `text` to its position in `outer`, where `text` is a
+    /// subslice of `outer`.
+    pub fn within(mut self, outer: &str, text: &str) -> Self {
+        let (line, column) = position(outer, text);
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line += line - 1;
+        self
+    }
+
+    pub fn in_day(mut self, day: usize) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// Renders the error with the offending line and a caret under the bad token.
+    pub fn diagnostic(&self, input: &str, source: &str) -> String {
+        let text = input.lines().nth(self.line - 1).unwrap_or("");
+        let gutter = " ".repeat(self.line.to_string().len());
+        let indent = " ".repeat(self.column - 1);
+        let carets = "^".repeat(self.found.chars().count().max(1));
+        format!(
+            "error: {self}\n{gutter}--> {source}:{}:{}\n{gutter} |\n{} | {text}\n{gutter} | {indent}{carets}",
+            self.line, self.column, self.line
+        )
+    }
+}
+
+/// 1-based line and column of `at` within `text`.
+fn position(text: &str, at: &str) -> (usize, usize) {
+    let offset = at.as_ptr() as usize - text.as_ptr() as usize;
+    assert!(offset <= text.len(), "parse error outside of its input");
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}: ")?;
+        }
+        write!(f, "expected {}, ", self.expected)?;
+        if self.found.is_empty() {
+            write!(f, "found end of line")
+        } else {
+            write!(f, "found `{}`", self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Why a part has no answer for an input that parsed, such as a route that never arrives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolveError {
+    pub message: String,
+}
+
+impl SolveError {
+    pub fn new(message: impl Into<String>) -> Self {
+        SolveError {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for SolveError {}
+
+/// Parses every line of `input` with `f`, placing any error on the line it came from.
+pub fn parse_lines<T, F>(input: &str, f: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .map(|line| f(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+/// Parses a number, reporting `token` as the bad text if it is not one.
+pub fn parse_number<T: std::str::FromStr>(line: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(line, token, "a number"))
+}
+
+#[test]
+fn test() {
+    let input = "Game 1: 3 red\nGame x: 1 blue";
+    let line = input.lines().nth(1).unwrap();
+    let err = ParseError::at(line, &line[5..6], "a number")
+        .within(input, line)
+        .in_day(2);
+    assert_eq!((err.line, err.column), (2, 6));
+    assert_eq!(err.to_string(), "day 2: expected a number, found `x`");
+    assert_eq!(
+        err.diagnostic(input, "inputs/day02.txt"),
+        "error: day 2: expected a number, found `x`
+ --> inputs/day02.txt:2:6
+  |
+2 | Game x: 1 blue
+  |      ^"
+    );
+
+    let err = ParseError::at_end(line, "`;`");
+    assert_eq!((err.line, err.column, err.found.as_str()), (1, 15, ""));
+}
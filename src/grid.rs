@@ -0,0 +1,217 @@
+//! A rectangular grid stored row by row in one `Vec`, for the days whose input is a map.
+
+use std::ops::{Index, IndexMut};
+
+use crate::error::parse_lines;
+use crate::ParseError;
+
+/// Offsets to the four orthogonal neighbours.
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets to all eight neighbours, row by row.
+const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Cells are addressed as `(x, y)`, with `x` the column and `y` the row, both from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses one row per line, turning each character into a cell with `cell`. `expected`
+    /// describes a valid character for the error when `cell` returns `None`. Every row
+    /// must be as wide as the first.
+    pub fn parse<F>(input: &str, expected: &str, cell: F) -> Result<Self, ParseError>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        let width = input.lines().next().map_or(0, |l| l.chars().count());
+        let rows =
+            parse_lines(input, |line| {
+                let mut row = Vec::with_capacity(width);
+                for (i, c) in line.char_indices() {
+                    if row.len() == width {
+                        return Err(ParseError::at(line, &line[i..], "the end of the row"));
+                    }
+                    let value = cell(c);
+                    row.push(value.ok_or_else(|| {
+                        ParseError::at(line, &line[i..i + c.len_utf8()], expected)
+                    })?);
+                }
+                if row.len() < width {
+                    return Err(ParseError::at_end(
+                        line,
+                        format!("a row {width} characters wide"),
+                    ));
+                }
+                Ok(row)
+            })?;
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Like [`Grid::parse`], but rows may have different lengths. Short rows are padded
+    /// with `fill` to the width of the longest.
+    pub fn parse_padded<F>(
+        input: &str,
+        fill: T,
+        expected: &str,
+        cell: F,
+    ) -> Result<Self, ParseError>
+    where
+        T: Clone,
+        F: Fn(char) -> Option<T>,
+    {
+        let width = input.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        let rows =
+            parse_lines(input, |line| {
+                let mut row = Vec::with_capacity(width);
+                for (i, c) in line.char_indices() {
+                    let value = cell(c);
+                    row.push(value.ok_or_else(|| {
+                        ParseError::at(line, &line[i..i + c.len_utf8()], expected)
+                    })?);
+                }
+                row.resize(width, fill.clone());
+                Ok(row)
+            })?;
+        Ok(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    fn offsets(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+            (x < self.width && y < self.height).then_some((x, y))
+        })
+    }
+
+    /// Positions of the up to four cells sharing an edge with `(x, y)`.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(x, y, &ORTHOGONAL)
+    }
+
+    /// Positions of the up to eight cells touching `(x, y)`, diagonals included.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offsets(x, y, &SURROUNDING)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside the grid"))
+    }
+}
+
+#[test]
+fn test() {
+    let grid = Grid::parse("123\n456", "a digit", |c| c.to_digit(10)).unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid.get(2, 1), Some(&6));
+    assert_eq!(grid.get(3, 0), None);
+    assert_eq!(grid[(0, 1)], 4);
+    assert_eq!(grid.row(1), [4, 5, 6]);
+    assert_eq!(grid.rows().count(), 2);
+    assert_eq!(grid.column(1).collect::<Vec<_>>(), [&2, &5]);
+    assert_eq!(grid.iter().nth(4), Some(((1, 1), &5)));
+
+    let mut corner: Vec<_> = grid.neighbours8(0, 0).collect();
+    corner.sort();
+    assert_eq!(corner, [(0, 1), (1, 0), (1, 1)]);
+    assert_eq!(grid.neighbours8(1, 0).count(), 5);
+    assert_eq!(grid.neighbours4(1, 1).count(), 3);
+
+    let err = Grid::parse("12\n3x", "a digit", |c| c.to_digit(10)).unwrap_err();
+    assert_eq!((err.line, err.column, err.found.as_str()), (2, 2, "x"));
+    let err = Grid::parse("12\n345", "a digit", |c| c.to_digit(10)).unwrap_err();
+    assert_eq!((err.line, err.column), (2, 3));
+    assert!(Grid::parse("12\n3", "a digit", |c| c.to_digit(10)).is_err());
+
+    let padded = Grid::parse_padded("1\n234\n", 0, "a digit", |c| c.to_digit(10)).unwrap();
+    assert_eq!((padded.width(), padded.height()), (3, 2));
+    assert_eq!(padded.row(0), [1, 0, 0]);
+    let err = Grid::parse_padded("1\n2x", 0, "a digit", |c| c.to_digit(10)).unwrap_err();
+    assert_eq!((err.line, err.column), (2, 2));
+}
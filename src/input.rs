@@ -0,0 +1,56 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// Directory puzzle inputs are read from unless overridden on the command line.
+pub const INPUT_DIR: &str = "inputs";
+
+/// Where a day's puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `inputs/dayNN.txt`
+    Default,
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn describe(&self, day: usize) -> String {
+        match self {
+            InputSource::Default => default_path(day).display().to_string(),
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => "<stdin>".to_string(),
+        }
+    }
+}
+
+pub fn default_path(day: usize) -> PathBuf {
+    [INPUT_DIR, &format!("day{day:02}.txt")].iter().collect()
+}
+
+pub fn load(day: usize, source: &InputSource) -> io::Result<String> {
+    match source {
+        InputSource::Default => fs::read_to_string(default_path(day)),
+        InputSource::File(path) => fs::read_to_string(path),
+        InputSource::Stdin => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+/// A stable fingerprint of a puzzle input (64-bit FNV-1a), so results can be matched to
+/// the input they came from without storing it.
+pub fn hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325_u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+#[test]
+fn test_hash() {
+    assert_eq!(hash(""), "cbf29ce484222325");
+    assert_eq!(hash("a"), "af63dc4c8601ec8c");
+}
@@ -0,0 +1,86 @@
+//! Just enough JSON writing for machine-readable output, without pulling in a serializer.
+
+use std::fmt::{Display, Write};
+
+/// Quotes and escapes `s` as a JSON string.
+pub fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Joins already-encoded JSON values into an array.
+pub fn array<I>(values: I) -> String
+where
+    I: IntoIterator<Item = String>,
+{
+    let values: Vec<String> = values.into_iter().collect();
+    format!("[{}]", values.join(","))
+}
+
+/// Builds a JSON object one field at a time, keeping fields in insertion order.
+#[derive(Debug, Default)]
+pub struct Object {
+    fields: Vec<String>,
+}
+
+impl Object {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn string(self, key: &str, value: &str) -> Self {
+        self.raw(key, &string(value))
+    }
+
+    pub fn number(self, key: &str, value: impl Display) -> Self {
+        self.raw(key, &value.to_string())
+    }
+
+    pub fn bool(self, key: &str, value: bool) -> Self {
+        self.raw(key, if value { "true" } else { "false" })
+    }
+
+    /// Adds a value that is already encoded as JSON, such as a nested object.
+    pub fn raw(mut self, key: &str, value: &str) -> Self {
+        self.fields.push(format!("{}:{value}", string(key)));
+        self
+    }
+
+    pub fn finish(self) -> String {
+        format!("{{{}}}", self.fields.join(","))
+    }
+}
+
+#[test]
+fn test() {
+    assert_eq!(string("a \"b\"\n\\"), r#""a \"b\"\n\\""#);
+    assert_eq!(string("\u{1}"), r#""\u0001""#);
+
+    let inner = Object::new().number("min_ns", 5).finish();
+    let object = Object::new()
+        .number("day", 1)
+        .string("answer", "55172")
+        .bool("ok", true)
+        .raw("bench", &inner)
+        .raw("parts", &array(["1".to_string(), "2".to_string()]))
+        .finish();
+    assert_eq!(
+        object,
+        r#"{"day":1,"answer":"55172","ok":true,"bench":{"min_ns":5},"parts":[1,2]}"#
+    );
+    assert_eq!(Object::new().finish(), "{}");
+    assert_eq!(array(Vec::new()), "[]");
+}
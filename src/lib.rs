@@ -0,0 +1,17 @@
+pub mod aho_corasick;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+mod error;
+pub mod grid;
+pub mod json;
+pub mod rng;
+mod solution;
+
+pub use error::{ParseError, SolveError};
+pub use solution::{Answer, Solution};
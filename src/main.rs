@@ -1,14 +1,287 @@
-#![feature(ascii_char)]
-#![feature(iter_array_chunks)]
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
+use std::env;
+use std::process;
+
+use aoc2023::{
+    day1, day2, day3, day4, day5, day6, day7, day8, Answer, ParseError, Solution, SolveError,
+};
+
+use answers::{Answers, Status};
+use report::Format;
+use timing::{time, Bench, Timings};
+
+mod answers;
+mod cli;
+mod input;
+mod parallel;
+mod report;
+mod timing;
+
+/// Which parts of a day's puzzle to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parts {
+    One,
+    Two,
+    Both,
+}
+
+impl Parts {
+    pub fn one(self) -> bool {
+        matches!(self, Parts::One | Parts::Both)
+    }
+
+    pub fn two(self) -> bool {
+        matches!(self, Parts::Two | Parts::Both)
+    }
+}
+
+/// The displayed answers of the parts that were run.
+type DayAnswers = [Option<String>; 2];
+
+struct DayRun {
+    answers: DayAnswers,
+    /// Why a part that was run has no answer.
+    errors: [Option<String>; 2],
+    timings: Timings,
+}
+
+struct Day {
+    number: usize,
+    run: fn(&str, Parts) -> Result<DayRun, ParseError>,
+}
+
+const fn day<S: Solution>() -> Day {
+    Day {
+        number: S::DAY,
+        run: run::<S>,
+    }
+}
+
+/// Every solved day, in order. Adding a day means adding its module and an entry here.
+const DAYS: &[Day] = &[
+    day::<day1::Day1>(),
+    day::<day2::Day2>(),
+    day::<day3::Day3>(),
+    day::<day4::Day4>(),
+    day::<day5::Day5>(),
+    day::<day6::Day6>(),
+    day::<day7::Day7>(),
+    day::<day8::Day8>(),
+];
+
+fn run<S: Solution>(input: &str, parts: Parts) -> Result<DayRun, ParseError> {
+    let (parsed, parse) = time(|| S::parse(input));
+    let parsed = parsed.map_err(|e| e.in_day(S::DAY))?;
+    let mut run = DayRun {
+        answers: [None, None],
+        errors: [None, None],
+        timings: Timings {
+            parse,
+            parts: [None, None],
+        },
+    };
+    if parts.one() {
+        let (answer, elapsed) = time(|| S::part1(&parsed));
+        run.record(0, answer.into_answer());
+        run.timings.parts[0] = Some(elapsed);
+    }
+    if parts.two() {
+        let (answer, elapsed) = time(|| S::part2(&parsed));
+        run.record(1, answer.into_answer());
+        run.timings.parts[1] = Some(elapsed);
+    }
+    Ok(run)
+}
+
+impl DayRun {
+    fn record(&mut self, part: usize, answer: Result<String, SolveError>) {
+        match answer {
+            Ok(answer) => self.answers[part] = Some(answer),
+            Err(err) => self.errors[part] = Some(err.to_string()),
+        }
+    }
+}
+
+/// What happened when a day was run on a worker thread.
+enum Outcome {
+    Ran {
+        run: DayRun,
+        bench: Option<Bench>,
+        input_hash: String,
+    },
+    /// The day could not be run. `diagnostic` is the full message for stderr.
+    Failed { error: String, diagnostic: String },
+}
+
+fn execute(day: &Day, args: &cli::Args) -> Outcome {
+    let source = args.input.describe(day.number);
+    let input = match input::load(day.number, &args.input) {
+        Ok(input) => input,
+        Err(err) => {
+            let error = format!("day {}: could not read {source}: {err}", day.number);
+            return Outcome::Failed {
+                diagnostic: format!("error: {error}"),
+                error,
+            };
+        }
+    };
+    let run = match (day.run)(&input, args.parts) {
+        Ok(run) => run,
+        Err(err) => {
+            return Outcome::Failed {
+                error: err.to_string(),
+                diagnostic: err.diagnostic(&input, &source),
+            }
+        }
+    };
+
+    let bench = args.bench.map(|n| {
+        let mut runs = vec![run.timings];
+        // The input already parsed once, so the repeats cannot fail
+        runs.extend((1..n).map(|_| {
+            let run = (day.run)(&input, args.parts);
+            run.map(|r| r.timings).unwrap_or_default()
+        }));
+        Bench {
+            day: day.number,
+            runs,
+        }
+    });
+    Outcome::Ran {
+        run,
+        bench,
+        input_hash: input::hash(&input),
+    }
+}
+
+fn find_day(number: usize) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
 
 fn main() {
-    day8::solve();
+    let args = match cli::parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(cli::ArgsError::Help) => {
+            println!("{}", cli::USAGE);
+            return;
+        }
+        Err(cli::ArgsError::Invalid(msg)) => {
+            eprintln!("error: {msg}");
+            eprintln!("{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
+
+    let days: Vec<&Day> = if args.all {
+        DAYS.iter().collect()
+    } else if args.days.is_empty() {
+        // With no selection, run the most recent day
+        DAYS.last().into_iter().collect()
+    } else {
+        let mut days = Vec::with_capacity(args.days.len());
+        for n in &args.days {
+            match find_day(*n) {
+                Some(day) => days.push(day),
+                None => {
+                    eprintln!("error: day {n} has no solution");
+                    process::exit(2);
+                }
+            }
+        }
+        days
+    };
+
+    let recorded = match Answers::load(&args.answers) {
+        Ok(answers) => answers,
+        Err(msg) => {
+            eprintln!("{msg}");
+            process::exit(1);
+        }
+    };
+    let mut updated = recorded.clone();
+
+    let mut failed = false;
+    let mut mismatched = false;
+    let mut benches = Vec::new();
+    // Days running side by side compete for the CPU, which would skew their timings
+    let jobs = if args.bench.is_some() { 1 } else { args.jobs };
+    parallel::run_ordered(
+        &days,
+        jobs,
+        |day| execute(day, &args),
+        |day, outcome| {
+            let outcome = outcome.unwrap_or_else(|msg| {
+                let error = format!("day {} panicked: {msg}", day.number);
+                Outcome::Failed {
+                    diagnostic: format!("error: {error}"),
+                    error,
+                }
+            });
+            let (run, bench, input_hash) = match outcome {
+                Outcome::Ran {
+                    run,
+                    bench,
+                    input_hash,
+                } => (run, bench, input_hash),
+                Outcome::Failed { error, diagnostic } => {
+                    eprintln!("{diagnostic}");
+                    if args.format == Format::Json {
+                        println!("{}", report::json_error(day.number, &error));
+                    }
+                    failed = true;
+                    return;
+                }
+            };
+
+            let mut statuses = [None, None];
+            for (i, answer) in run.answers.iter().enumerate() {
+                if let Some(answer) = answer {
+                    let status = recorded.check(day.number, &input_hash, i + 1, answer);
+                    mismatched |= matches!(status, Status::Fail { .. });
+                    statuses[i] = Some(status);
+                    updated.record(day.number, &input_hash, i + 1, answer);
+                }
+            }
+
+            match args.format {
+                Format::Text => report::print_text(day.number, &run, &statuses),
+                Format::Json => {
+                    let records = report::json_records(
+                        day.number,
+                        &run,
+                        &statuses,
+                        &input_hash,
+                        bench.as_ref(),
+                    );
+                    records.iter().for_each(|r| println!("{r}"));
+                }
+            }
+            // A part without an answer fails the run and is never recorded
+            for (i, err) in run.errors.iter().enumerate() {
+                if let Some(err) = err {
+                    let error = format!("day {} part {}: {err}", day.number, i + 1);
+                    eprintln!("error: {error}");
+                    if args.format == Format::Json {
+                        println!("{}", report::json_error(day.number, &error));
+                    }
+                    failed = true;
+                }
+            }
+            benches.extend(bench);
+        },
+    );
+    if let (Some(n), Format::Text) = (args.bench, args.format) {
+        println!("\nBenchmark ({n} runs)");
+        print!("{}", timing::summary_table(&benches));
+    }
+    if args.record {
+        if let Err(err) = updated.save(&args.answers) {
+            eprintln!("error: could not write {}: {err}", args.answers.display());
+            process::exit(1);
+        }
+        eprintln!("Recorded answers to {}", args.answers.display());
+    }
+    // Mismatches are expected when re-recording, so only errors fail the run then
+    if failed || (mismatched && !args.record) {
+        process::exit(1);
+    }
 }
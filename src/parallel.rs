@@ -0,0 +1,106 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+/// Name given to worker threads, so the panic hook can tell them apart.
+const WORKER: &str = "run_ordered worker";
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(msg) => *msg,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(msg) => msg.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+/// Runs `f` on every item using `jobs` worker threads and hands each result to `emit` on
+/// the calling thread, in item order, as soon as it and everything before it are done.
+///
+/// A panic in `f` is caught and passed to `emit` as `Err` with the panic message, so one
+/// bad item does not stop the others. The panic hook is silenced on the workers while
+/// this runs, so the message is not also printed to stderr.
+pub fn run_ordered<T, R, F, E>(items: &[T], jobs: usize, f: F, mut emit: E)
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+    E: FnMut(&T, Result<R, String>),
+{
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    let previous = Arc::new(panic::take_hook());
+    let hook = Arc::clone(&previous);
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() != Some(WORKER) {
+            hook(info);
+        }
+    }));
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let tx = tx.clone();
+            let (next, f) = (&next, &f);
+            thread::Builder::new()
+                .name(WORKER.to_string())
+                .spawn_scoped(scope, move || loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else { break };
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| f(item)));
+                    if tx.send((i, result.map_err(panic_message))).is_err() {
+                        break;
+                    }
+                })
+                .expect("failed to spawn a worker thread");
+        }
+        drop(tx);
+
+        let mut pending: Vec<Option<Result<R, String>>> = items.iter().map(|_| None).collect();
+        let mut emitted = 0;
+        for (i, result) in rx {
+            pending[i] = Some(result);
+            while let Some(result) = pending.get_mut(emitted).and_then(Option::take) {
+                emit(&items[emitted], result);
+                emitted += 1;
+            }
+        }
+    });
+
+    // Dropping our hook releases its handle on the previous one
+    drop(panic::take_hook());
+    if let Ok(previous) = Arc::try_unwrap(previous) {
+        panic::set_hook(previous);
+    }
+}
+
+#[test]
+fn test() {
+    let items: Vec<u64> = (0..20).collect();
+    let mut seen = Vec::new();
+    run_ordered(
+        &items,
+        4,
+        |&n| {
+            // Finish out of order: later items are quicker
+            thread::sleep(std::time::Duration::from_millis(20 - n));
+            if n == 7 {
+                panic!("bad item {n}");
+            }
+            n * 2
+        },
+        |&n, result| seen.push((n, result)),
+    );
+
+    assert_eq!(seen.len(), 20);
+    for (i, (n, result)) in seen.into_iter().enumerate() {
+        assert_eq!(n, i as u64);
+        match result {
+            Ok(doubled) => assert_eq!(doubled, n * 2),
+            Err(msg) => assert_eq!((n, msg.as_str()), (7, "bad item 7")),
+        }
+    }
+}
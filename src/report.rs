@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use aoc2023::json::Object;
+
+use crate::answers::Status;
+use crate::timing::{format_duration, Bench, Stats};
+use crate::DayRun;
+
+/// How run results are written to stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    /// One JSON object per line for each day and part.
+    Json,
+}
+
+/// Prints a day's answers next to how they compare with the recorded ones.
+pub fn print_text(day: usize, run: &DayRun, statuses: &[Option<Status>; 2]) {
+    println!("Day {day} (parse {})", format_duration(run.timings.parse));
+    for (i, (answer, status)) in run.answers.iter().zip(statuses).enumerate() {
+        let (Some(answer), Some(status)) = (answer, status) else {
+            continue;
+        };
+        let elapsed = format_duration(run.timings.parts[i].unwrap_or_default());
+        match status {
+            Status::Fail { expected } => {
+                println!(
+                    "Part {}: {answer} [FAIL, expected {expected}] ({elapsed})",
+                    i + 1
+                );
+            }
+            _ => println!("Part {}: {answer} [{}] ({elapsed})", i + 1, status.label()),
+        }
+    }
+}
+
+fn nanos(d: Duration) -> u128 {
+    d.as_nanos()
+}
+
+fn bench_json(runs: usize, stats: Stats) -> String {
+    Object::new()
+        .number("runs", runs)
+        .number("min_ns", nanos(stats.min))
+        .number("median_ns", nanos(stats.median))
+        .number("mean_ns", nanos(stats.mean))
+        .finish()
+}
+
+/// One JSON object for each part that was run.
+pub fn json_records(
+    day: usize,
+    run: &DayRun,
+    statuses: &[Option<Status>; 2],
+    input_hash: &str,
+    bench: Option<&Bench>,
+) -> Vec<String> {
+    let mut records = Vec::new();
+    for (i, (answer, status)) in run.answers.iter().zip(statuses).enumerate() {
+        let (Some(answer), Some(status)) = (answer, status) else {
+            continue;
+        };
+        let part = i + 1;
+        let mut object = Object::new()
+            .number("day", day)
+            .number("part", part)
+            .string("answer", answer)
+            .string("status", &status.label().to_lowercase());
+        if let Status::Fail { expected } = status {
+            object = object.string("expected", expected);
+        }
+        object = object
+            .number("parse_ns", nanos(run.timings.parse))
+            .number("time_ns", nanos(run.timings.parts[i].unwrap_or_default()))
+            .string("input_hash", input_hash);
+        if let Some(bench) = bench {
+            if let Some(stats) = bench.part_stats(part) {
+                object = object.raw("bench", &bench_json(bench.runs.len(), stats));
+            }
+        }
+        records.push(object.finish());
+    }
+    records
+}
+
+/// A JSON object for a day that could not be run at all.
+pub fn json_error(day: usize, error: &str) -> String {
+    Object::new()
+        .number("day", day)
+        .string("status", "error")
+        .string("error", error)
+        .finish()
+}
+
+#[test]
+fn test_json_records() {
+    use crate::timing::Timings;
+
+    let run = DayRun {
+        answers: [Some("142".to_string()), Some("281".to_string())],
+        errors: [None, None],
+        timings: Timings {
+            parse: Duration::from_nanos(10),
+            parts: [
+                Some(Duration::from_nanos(20)),
+                Some(Duration::from_nanos(30)),
+            ],
+        },
+    };
+    let statuses = [
+        Some(Status::Pass),
+        Some(Status::Fail {
+            expected: "280".to_string(),
+        }),
+    ];
+    let records = json_records(1, &run, &statuses, "abc", None);
+    assert_eq!(
+        records,
+        [
+            r#"{"day":1,"part":1,"answer":"142","status":"pass","parse_ns":10,"time_ns":20,"input_hash":"abc"}"#,
+            r#"{"day":1,"part":2,"answer":"281","status":"fail","expected":"280","parse_ns":10,"time_ns":30,"input_hash":"abc"}"#,
+        ]
+    );
+    assert_eq!(
+        json_error(3, "missing"),
+        r#"{"day":3,"status":"error","error":"missing"}"#
+    );
+}
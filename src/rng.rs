@@ -0,0 +1,50 @@
+//! A small seeded random number generator (SplitMix64), so generated test data can be
+//! reproduced from its seed without pulling in a dependency. Not for anything that needs
+//! unpredictable numbers.
+
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0` to `n - 1`. `n` must not be 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "empty range");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// `true` with probability `percent` in 100.
+    pub fn percent(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+#[test]
+fn test() {
+    // Reference values for SplitMix64 seeded with 0
+    let mut rng = Rng::new(0);
+    assert_eq!(rng.next_u64(), 0xe220a8397b1dcdaf);
+    assert_eq!(rng.next_u64(), 0x6e789e6aa1b965f4);
+
+    let mut rng = Rng::new(7);
+    assert!((0..1000).all(|_| rng.below(6) < 6));
+    assert_eq!(Rng::new(7).next_u64(), Rng::new(7).next_u64());
+    assert_eq!(*Rng::new(1).choose(&[5]), 5);
+}
@@ -0,0 +1,42 @@
+use std::fmt::Display;
+
+use crate::{ParseError, SolveError};
+
+/// A day's puzzle, split into parsing and the two parts so each step can be called on its own.
+pub trait Solution {
+    const DAY: usize;
+
+    /// The puzzle input once parsed, shared by both parts.
+    type Input;
+    type Part1: Answer;
+    type Part2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+}
+
+/// What a part returns: a number, or a `Result` for parts that can fail on input that
+/// parsed.
+pub trait Answer {
+    /// The answer as it is shown and recorded.
+    fn into_answer(self) -> Result<String, SolveError>;
+}
+
+macro_rules! display_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn into_answer(self) -> Result<String, SolveError> {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+display_answer!(u32, u64, usize, i64);
+
+impl<T: Display> Answer for Result<T, SolveError> {
+    fn into_answer(self) -> Result<String, SolveError> {
+        self.map(|answer| answer.to_string())
+    }
+}
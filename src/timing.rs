@@ -0,0 +1,133 @@
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+/// How long each step of one run of a day took. Parts that were not run are `None`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub parts: [Option<Duration>; 2],
+}
+
+/// Runs `f`, returning its result and how long it took.
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let now = Instant::now();
+    let result = f();
+    (result, now.elapsed())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    /// Statistics over `samples`, which must not be empty.
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+        Stats {
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+        }
+    }
+}
+
+pub fn format_duration(d: Duration) -> String {
+    let ns = d.as_nanos();
+    if ns < 1_000 {
+        format!("{ns}ns")
+    } else if ns < 1_000_000 {
+        format!("{:.1}µs", ns as f64 / 1e3)
+    } else if ns < 1_000_000_000 {
+        format!("{:.1}ms", ns as f64 / 1e6)
+    } else {
+        format!("{:.2}s", d.as_secs_f64())
+    }
+}
+
+/// Timings from repeated runs of one day.
+pub struct Bench {
+    pub day: usize,
+    pub runs: Vec<Timings>,
+}
+
+impl Bench {
+    pub fn parse_stats(&self) -> Stats {
+        let samples: Vec<_> = self.runs.iter().map(|t| t.parse).collect();
+        Stats::new(&samples)
+    }
+
+    /// Stats for `part` (1 or 2), if it was run.
+    pub fn part_stats(&self, part: usize) -> Option<Stats> {
+        let samples: Vec<_> = self.runs.iter().filter_map(|t| t.parts[part - 1]).collect();
+        (!samples.is_empty()).then(|| Stats::new(&samples))
+    }
+
+    fn rows(&self) -> Vec<(&'static str, Stats)> {
+        let mut rows = vec![("parse", self.parse_stats())];
+        for (part, label) in [(1, "part 1"), (2, "part 2")] {
+            if let Some(stats) = self.part_stats(part) {
+                rows.push((label, stats));
+            }
+        }
+        rows
+    }
+}
+
+/// A table of min/median/mean for every step of every benchmarked day, with totals.
+pub fn summary_table(benches: &[Bench]) -> String {
+    let mut table = format!(
+        "{:>3}  {:<6}  {:>10}  {:>10}  {:>10}\n",
+        "Day", "Step", "Min", "Median", "Mean"
+    );
+    let mut total = [Duration::ZERO; 3];
+    let mut row = |day: &str, step: &str, stats: Stats| {
+        writeln!(
+            table,
+            "{day:>3}  {step:<6}  {:>10}  {:>10}  {:>10}",
+            format_duration(stats.min),
+            format_duration(stats.median),
+            format_duration(stats.mean)
+        )
+        .unwrap();
+    };
+    for bench in benches {
+        for (step, stats) in bench.rows() {
+            row(&bench.day.to_string(), step, stats);
+            total[0] += stats.min;
+            total[1] += stats.median;
+            total[2] += stats.mean;
+        }
+    }
+    let [min, median, mean] = total;
+    row("", "total", Stats { min, median, mean });
+    table
+}
+
+#[test]
+fn test() {
+    let ms = Duration::from_millis;
+    let stats = Stats::new(&[ms(4), ms(1), ms(3), ms(8)]);
+    assert_eq!(
+        stats,
+        Stats {
+            min: ms(1),
+            median: ms(3) + Duration::from_micros(500),
+            mean: ms(4),
+        }
+    );
+    assert_eq!(Stats::new(&[ms(2), ms(9), ms(1)]).median, ms(2));
+
+    assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+    assert_eq!(format_duration(Duration::from_micros(1500)), "1.5ms");
+    assert_eq!(format_duration(Duration::from_secs(3)), "3.00s");
+}